@@ -524,12 +524,13 @@ async function findUp(dir = process.cwd()): Promise<string | null> {
 }
 
 interface TypeDef {
-  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface'
+  kind: 'fn' | 'struct' | 'impl' | 'enum' | 'interface' | 'type'
   name: string
   original_name?: string
   def: string
   js_mod?: string
   js_doc: string
+  extends?: string
 }
 
 async function processIntermediateTypeFile(
@@ -558,7 +559,7 @@ async function processIntermediateTypeFile(
   function convertDefs(defs: TypeDef[], nested = false): string {
     const classes = new Map<
       string,
-      { def: string; js_doc: string; original_name?: string }
+      { def: string; js_doc: string; original_name?: string; extends?: string }
     >()
     const impls = new Map<string, string>()
     let dts = ''
@@ -573,6 +574,7 @@ async function processIntermediateTypeFile(
           classes.set(def.name, {
             original_name: def.original_name,
             def: def.def,
+            extends: def.extends,
             js_doc: def.js_doc,
           })
           break
@@ -600,22 +602,36 @@ async function processIntermediateTypeFile(
           dts += indentLines(def.def, nest + 2) + '\n'
           dts += indentLines(`}`, nest) + '\n'
           break
+        case 'type':
+          dts +=
+            indentLines(
+              `${def.js_doc}export type ${def.name} = ${def.def}`,
+              nest,
+            ) + '\n'
+          break
         default:
-          if (!nested) {
+          // overloaded functions share the same name
+          if (!nested && !idents.includes(def.name)) {
             idents.push(def.name)
           }
           dts += indentLines(`${def.js_doc}${def.def}`, nest) + '\n'
       }
     })
 
-    for (const [name, { js_doc, def, original_name }] of classes.entries()) {
+    for (const [
+      name,
+      { js_doc, def, original_name, extends: parent },
+    ] of classes.entries()) {
       const implDef = impls.get(name)
 
       if (original_name && name !== original_name) {
         dts += indentLines(`export type ${original_name} = ${name}\n`, nest)
       }
 
-      dts += indentLines(`${js_doc}export class ${name} {`, nest)
+      dts += indentLines(
+        `${js_doc}export class ${name}${parent ? ` extends ${parent}` : ''} {`,
+        nest,
+      )
 
       if (def) {
         dts += '\n' + indentLines(def, nest + 2)
@@ -22,6 +22,10 @@ pub struct NapiFn {
   pub skip_typescript: bool,
   pub comments: Vec<String>,
   pub parent_is_generator: bool,
+  pub parent_is_async_generator: bool,
+  pub catch_unwind: bool,
+  /// `#[napi(overload)]`, one of the functions exported under the same `js_name`
+  pub overload: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +39,9 @@ pub struct CallbackArg {
 pub struct NapiFnArg {
   pub kind: NapiFnArgKind,
   pub ts_arg_type: Option<String>,
+  /// `#[napi(default = ...)]`, used if the argument is omitted, `undefined` or `null`.
+  /// It's wrapped in `Some` for the `Option<T>` argument.
+  pub default: Option<syn::Lit>,
 }
 
 impl NapiFnArg {
@@ -46,6 +53,29 @@ impl NapiFnArg {
       .map(|ts| ts.clone())
       .unwrap_or_else(default)
   }
+
+  /// `Rest<T>` collects the remaining arguments of the function
+  pub fn is_rest(&self) -> bool {
+    self.is_type_named("Rest")
+  }
+
+  /// `This<T>` is the receiver of the function call, not one of the JavaScript arguments
+  pub fn is_this(&self) -> bool {
+    self.is_type_named("This")
+  }
+
+  fn is_type_named(&self, name: &str) -> bool {
+    match &self.kind {
+      NapiFnArgKind::PatType(path) => match path.ty.as_ref() {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path
+          .segments
+          .last()
+          .map_or(false, |segment| segment.ident == name),
+        _ => false,
+      },
+      NapiFnArgKind::Callback(_) => false,
+    }
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -81,6 +111,18 @@ pub struct NapiStruct {
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub implement_iterator: bool,
+  /// `#[napi(async_iterator)]`, the struct implements `AsyncGenerator`
+  pub implement_async_iterator: bool,
+  pub catch_unwind: bool,
+  pub extends: Option<NapiStructExtends>,
+}
+
+/// `#[napi(extends = Base)]`, the first field of the struct holds the `Base` class
+#[derive(Debug, Clone)]
+pub struct NapiStructExtends {
+  pub ty: syn::Path,
+  pub js_name: String,
+  pub member: syn::Member,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -111,6 +153,8 @@ pub struct NapiImpl {
   pub iterator_yield_type: Option<Type>,
   pub iterator_next_type: Option<Type>,
   pub iterator_return_type: Option<Type>,
+  /// The iterator types are from `AsyncGenerator` rather than `Generator`
+  pub is_async_iterator: bool,
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
 }
@@ -123,12 +167,30 @@ pub struct NapiEnum {
   pub js_mod: Option<String>,
   pub comments: Vec<String>,
   pub skip_typescript: bool,
+  pub kind: NapiEnumKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NapiEnumKind {
+  Numeric,
+  /// `#[napi(string_enum)]`, variants are converted from/to string literals
+  String,
+  /// `#[napi(error)]`, every variant is a subclass of the JavaScript `Error` class
+  Error,
+  /// Enum with data-carrying variants, converted from/to objects discriminated by the `tag` field.
+  /// The variant fields are nested under the `content` field if it's provided.
+  Tagged {
+    tag: String,
+    content: Option<String>,
+  },
 }
 
 #[derive(Debug, Clone)]
 pub struct NapiEnumVariant {
   pub name: Ident,
+  pub js_name: String,
   pub val: i32,
+  pub fields: Vec<NapiStructField>,
   pub comments: Vec<String>,
 }
 
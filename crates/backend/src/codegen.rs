@@ -28,6 +28,15 @@ fn get_register_ident(name: &str) -> Ident {
   Ident::new(&new_name, Span::call_site())
 }
 
+/// Convert the panic in `body` into `napi::Error`, or abort the process if the item is marked as `#[napi(abort_on_panic)]`
+fn gen_catch_unwind(catch_unwind: bool, body: TokenStream) -> TokenStream {
+  if catch_unwind {
+    quote! { napi::__private::catch_unwind(|| { #body }) }
+  } else {
+    quote! { napi::__private::abort_on_panic(|| { #body }) }
+  }
+}
+
 fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
   js_mod
     .map(|i| {
@@ -36,3 +45,18 @@ fn js_mod_to_token_stream(js_mod: Option<&String>) -> TokenStream {
     })
     .unwrap_or_else(|| quote! { None })
 }
+
+fn is_option_type(ty: &syn::Type) -> bool {
+  if let syn::Type::Path(syn::TypePath {
+    path: syn::Path { segments, .. },
+    ..
+  }) = ty
+  {
+    segments
+      .last()
+      .map(|last_path| last_path.ident == "Option")
+      .unwrap_or(false)
+  } else {
+    false
+  }
+}
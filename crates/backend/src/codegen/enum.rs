@@ -2,14 +2,27 @@ use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_register_ident, js_mod_to_token_stream},
-  BindgenResult, NapiEnum, TryToTokens,
+  codegen::{get_register_ident, is_option_type, js_mod_to_token_stream},
+  BindgenResult, NapiEnum, NapiEnumKind, NapiStructField, TryToTokens,
 };
 
 impl TryToTokens for NapiEnum {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
-    let register = self.gen_module_register();
-    let napi_value_conversion = self.gen_napi_value_map_impl();
+    let (register, napi_value_conversion) = match self.kind {
+      NapiEnumKind::Numeric => (self.gen_module_register(), self.gen_napi_value_map_impl()),
+      NapiEnumKind::String => (quote! {}, self.gen_string_napi_value_impl()),
+      NapiEnumKind::Error => (
+        self.gen_error_classes_register(),
+        self.gen_error_napi_value_impl(),
+      ),
+      NapiEnumKind::Tagged {
+        ref tag,
+        ref content,
+      } => (
+        quote! {},
+        self.gen_tagged_napi_value_impl(tag, content.as_deref()),
+      ),
+    };
 
     (quote! {
       #napi_value_conversion
@@ -100,6 +113,94 @@ impl NapiEnum {
     }
   }
 
+  fn gen_string_napi_value_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let mut from_napi_branches = vec![];
+    let mut to_napi_branches = vec![];
+
+    self.variants.iter().for_each(|v| {
+      let val = &v.js_name;
+      let v_name = &v.name;
+
+      from_napi_branches.push(quote! { #val => Ok(#name::#v_name) });
+      to_napi_branches.push(quote! { #name::#v_name => #val });
+    });
+
+    let expected = self
+      .variants
+      .iter()
+      .map(|v| format!("{:?}", v.js_name))
+      .collect::<Vec<_>>()
+      .join(" | ");
+
+    quote! {
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::String
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<napi::sys::napi_value> {
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::String)?;
+          // the string must be one of the variants, so `is_valid` could tell the overloads apart
+          <#name as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?;
+          Ok(std::ptr::null_mut())
+        }
+      }
+
+      impl napi::bindgen_prelude::FromNapiValue for #name {
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          let val = String::from_napi_value(env, napi_val).map_err(|e| {
+            napi::bindgen_prelude::error!(
+              e.status,
+              "Failed to convert napi value into enum `{}`. {}",
+              #name_str,
+              e,
+            )
+          })?;
+
+          match val.as_str() {
+            #(#from_napi_branches,)*
+            _ => {
+              Err(napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "value `{:?}` does not match any variant of enum `{}`, expect {}",
+                val,
+                #name_str,
+                #expected
+              ))
+            }
+          }
+        }
+      }
+
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: Self
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let val = match val {
+            #(#to_napi_branches,)*
+          };
+
+          <&str>::to_napi_value(env, val)
+        }
+      }
+    }
+  }
+
   fn gen_module_register(&self) -> TokenStream {
     let name_str = self.name.to_string();
     let js_name_lit = Literal::string(&format!("{}\0", &self.js_name));
@@ -108,7 +209,7 @@ impl NapiEnum {
     let mut define_properties = vec![];
 
     for variant in self.variants.iter() {
-      let name_lit = Literal::string(&format!("{}\0", variant.name));
+      let name_lit = Literal::string(&format!("{}\0", variant.js_name));
       let val_lit = Literal::i32_unsuffixed(variant.val);
 
       define_properties.push(quote! {
@@ -157,4 +258,363 @@ impl NapiEnum {
       }
     }
   }
+
+  fn gen_error_napi_value_impl(&self) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let mod_name = Ident::new(&format!("__napi_helper__{}", name_str), Span::call_site());
+    let mut create_instance_branches = vec![];
+    let mut class_keys = vec![];
+
+    for (index, variant) in self.variants.iter().enumerate() {
+      let v_name = &variant.name;
+      let v_name_str = Literal::string(&format!("::{}\0", v_name));
+      let js_name_lit = Literal::string(&format!("{}\0", variant.js_name));
+      class_keys.push(quote! { concat!(module_path!(), #v_name_str) });
+      let mut bindings = vec![];
+      let mut set_properties = vec![];
+
+      for (i, field) in variant.fields.iter().enumerate() {
+        if !field.getter {
+          continue;
+        }
+        let member = &field.name;
+        let binding = Ident::new(&format!("field_{}", i), Span::call_site());
+        let field_js_name = &field.js_name;
+        let field_js_name_lit = Literal::string(&format!("{}\0", field.js_name));
+        bindings.push(quote! { #member: #binding });
+        set_properties.push(quote! {
+          napi::bindgen_prelude::check_status!(
+            napi::bindgen_prelude::sys::napi_set_named_property(
+              env,
+              instance,
+              #field_js_name_lit.as_ptr() as *const _,
+              napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #binding.clone())?,
+            ),
+            "Failed to set property `{}` of Error class `{}`",
+            #field_js_name,
+            #js_name_lit,
+          )?;
+        });
+      }
+
+      create_instance_branches.push(quote! {
+        super::#name::#v_name { #(#bindings,)* .. } => {
+          let instance = napi::__private::new_error_instance(env, CLASS_KEYS[#index], err.to_string())?;
+          #(#set_properties)*
+          Ok(instance)
+        }
+      });
+    }
+
+    let variants_len = self.variants.len();
+    quote! {
+      #[allow(clippy::all)]
+      #[allow(non_snake_case)]
+      mod #mod_name {
+        use super::*;
+
+        /// The Rust paths of the variants, which the variant classes are registered by
+        pub(super) const CLASS_KEYS: [&str; #variants_len] = [#(#class_keys),*];
+
+        pub(super) unsafe fn create_instance(
+          env: napi::bindgen_prelude::sys::napi_env,
+          err: &super::#name,
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          match err {
+            #(#create_instance_branches)*
+          }
+        }
+      }
+
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: Self
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          #mod_name::create_instance(env, &val)
+        }
+      }
+
+      impl From<#name> for napi::bindgen_prelude::Error {
+        fn from(err: #name) -> Self {
+          napi::bindgen_prelude::Error::from_reason(err.to_string())
+            .with_value_creator(move |env| unsafe { #mod_name::create_instance(env, &err) })
+        }
+      }
+
+      impl From<#name> for napi::bindgen_prelude::JsError {
+        fn from(err: #name) -> Self {
+          napi::bindgen_prelude::JsError::from(napi::bindgen_prelude::Error::from(err))
+        }
+      }
+    }
+  }
+
+  fn gen_error_classes_register(&self) -> TokenStream {
+    let name_str = self.name.to_string();
+    let js_name_lit = Literal::string(&format!("{}\0", &self.js_name));
+    let register_name = get_register_ident(&name_str);
+    let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+
+    let mod_name = Ident::new(&format!("__napi_helper__{}", name_str), Span::call_site());
+    let variants_register = self.variants.iter().enumerate().map(|(index, variant)| {
+      let variant_js_name_lit = Literal::string(&format!("{}\0", variant.js_name));
+      quote! {
+        napi::__private::register_error_variant_class(
+          #mod_name::CLASS_KEYS[#index],
+          #js_name_lit,
+          #variant_js_name_lit,
+        );
+      }
+    });
+
+    quote! {
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[cfg(all(not(test), not(feature = "noop")))]
+      #[napi::bindgen_prelude::ctor]
+      fn #register_name() {
+        napi::__private::register_class(
+          #name_str,
+          #js_mod_ident,
+          #js_name_lit,
+          vec![napi::bindgen_prelude::Property::new("constructor").unwrap().with_ctor(napi::__private::error_class_constructor)],
+        );
+        napi::__private::register_class_parent(#js_name_lit, "Error\0");
+        #(#variants_register)*
+      }
+    }
+  }
+
+  fn gen_tagged_napi_value_impl(&self, tag: &str, content: Option<&str>) -> TokenStream {
+    let name = &self.name;
+    let name_str = self.name.to_string();
+    let mut from_napi_branches = vec![];
+    let mut to_napi_branches = vec![];
+
+    for variant in self.variants.iter() {
+      let v_name = &variant.name;
+      let v_js_name = &variant.js_name;
+      let mut bindings = vec![];
+      let mut exposed_bindings = vec![];
+      let mut getters = vec![];
+      let mut setters = vec![];
+
+      let is_tuple = matches!(
+        variant.fields.first(),
+        Some(NapiStructField {
+          name: syn::Member::Unnamed(_),
+          ..
+        })
+      );
+      let exposed_fields = variant.fields.iter().filter(|f| f.getter).count();
+
+      for (i, field) in variant.fields.iter().enumerate() {
+        let member = &field.name;
+        let binding = Ident::new(&format!("field_{}", i), Span::call_site());
+        let ty = &field.ty;
+        let field_js_name = &field.js_name;
+        bindings.push(quote! { #member: #binding });
+
+        if !field.getter {
+          getters.push(quote! { let #binding: #ty = Default::default(); });
+          continue;
+        }
+        exposed_bindings.push(quote! { #member: #binding });
+
+        let is_optional = is_option_type(ty);
+        let missing_field = quote! {
+          .ok_or_else(|| napi::bindgen_prelude::Error::new(
+            napi::bindgen_prelude::Status::InvalidArg,
+            format!("Missing field `{}` of variant `{}`", #field_js_name, #v_js_name),
+          ))?
+        };
+
+        match content {
+          // tuple variant in adjacently tagged enum, the content is the value itself or an array
+          Some(content) if is_tuple => {
+            if exposed_fields == 1 {
+              getters.push(if is_optional {
+                quote! { let #binding: #ty = obj.get(#content)?; }
+              } else {
+                quote! { let #binding: #ty = obj.get(#content)? #missing_field; }
+              });
+              setters.push(quote! { obj.set(#content, #binding)?; });
+            } else {
+              let index = Literal::u32_unsuffixed(setters.len() as u32);
+              getters.push(if is_optional {
+                quote! { let #binding: #ty = content.get(#index)?.flatten(); }
+              } else {
+                quote! {
+                  let #binding: #ty = content.get(#index)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+                    napi::bindgen_prelude::Status::InvalidArg,
+                    format!("Missing element {} in field `{}` of variant `{}`", #index, #content, #v_js_name),
+                  ))?;
+                }
+              });
+              setters.push(quote! { content.set(#index, #binding)?; });
+            }
+          }
+          _ => {
+            let target = if content.is_some() {
+              quote! { content }
+            } else {
+              quote! { obj }
+            };
+            getters.push(if is_optional {
+              quote! { let #binding: #ty = #target.get(#field_js_name)?; }
+            } else {
+              quote! { let #binding: #ty = #target.get(#field_js_name)? #missing_field; }
+            });
+            setters.push(if is_optional {
+              quote! {
+                if #binding.is_some() {
+                  #target.set(#field_js_name, #binding)?;
+                }
+              }
+            } else {
+              quote! { #target.set(#field_js_name, #binding)?; }
+            });
+          }
+        }
+      }
+
+      let (get_content, set_content) = match content {
+        Some(content) if exposed_fields > 0 && !(is_tuple && exposed_fields == 1) => {
+          let missing_content = quote! {
+            .ok_or_else(|| napi::bindgen_prelude::Error::new(
+              napi::bindgen_prelude::Status::InvalidArg,
+              format!("Missing field `{}` of variant `{}`", #content, #v_js_name),
+            ))?
+          };
+          if is_tuple {
+            let len = Literal::u32_unsuffixed(exposed_fields as u32);
+            (
+              quote! { let content: napi::bindgen_prelude::Array = obj.get(#content)? #missing_content; },
+              quote! {
+                let mut content = env_wrapper.create_array(#len)?;
+                #(#setters)*
+                obj.set(#content, content)?;
+              },
+            )
+          } else {
+            (
+              quote! { let content: napi::bindgen_prelude::Object = obj.get(#content)? #missing_content; },
+              quote! {
+                let mut content = env_wrapper.create_object()?;
+                #(#setters)*
+                obj.set(#content, content)?;
+              },
+            )
+          }
+        }
+        _ => (quote! {}, quote! { #(#setters)* }),
+      };
+
+      from_napi_branches.push(quote! {
+        #v_js_name => {
+          #get_content
+          #(#getters)*
+          Ok(#name::#v_name { #(#bindings,)* })
+        }
+      });
+      to_napi_branches.push(quote! {
+        #name::#v_name { #(#exposed_bindings,)* .. } => {
+          obj.set(#tag, #v_js_name)?;
+          #set_content
+        }
+      });
+    }
+
+    let expected = self
+      .variants
+      .iter()
+      .map(|v| format!("{:?}", v.js_name))
+      .collect::<Vec<_>>()
+      .join(" | ");
+
+    quote! {
+      impl napi::bindgen_prelude::TypeName for #name {
+        fn type_name() -> &'static str {
+          #name_str
+        }
+
+        fn value_type() -> napi::ValueType {
+          napi::ValueType::Object
+        }
+      }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<napi::sys::napi_value> {
+          napi::bindgen_prelude::assert_type_of!(env, napi_val, napi::bindgen_prelude::ValueType::Object)?;
+          Ok(std::ptr::null_mut())
+        }
+      }
+
+      impl napi::bindgen_prelude::FromNapiValue for #name {
+        #[allow(clippy::all)]
+        unsafe fn from_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<Self> {
+          let obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val).map_err(|e| {
+            napi::bindgen_prelude::error!(
+              e.status,
+              "Failed to convert napi value into enum `{}`. {}",
+              #name_str,
+              e,
+            )
+          })?;
+          let tag: String = obj.get(#tag)?.ok_or_else(|| napi::bindgen_prelude::Error::new(
+            napi::bindgen_prelude::Status::InvalidArg,
+            format!("Missing tag field `{}` of enum `{}`", #tag, #name_str),
+          ))?;
+
+          match tag.as_str() {
+            #(#from_napi_branches,)*
+            _ => {
+              Err(napi::bindgen_prelude::error!(
+                napi::bindgen_prelude::Status::InvalidArg,
+                "tag `{:?}` does not match any variant of enum `{}`, expect {}",
+                tag,
+                #name_str,
+                #expected
+              ))
+            }
+          }
+        }
+      }
+
+      impl napi::bindgen_prelude::ToNapiValue for #name {
+        #[allow(clippy::all)]
+        unsafe fn to_napi_value(
+          env: napi::bindgen_prelude::sys::napi_env,
+          val: Self
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let env_wrapper = napi::bindgen_prelude::Env::from(env);
+          let mut obj = env_wrapper.create_object()?;
+
+          match val {
+            #(#to_napi_branches,)*
+          };
+
+          napi::bindgen_prelude::Object::to_napi_value(env, obj)
+        }
+      }
+    }
+  }
 }
@@ -2,10 +2,31 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
+  codegen::{
+    gen_catch_unwind, get_intermediate_ident, get_register_ident, is_option_type,
+    js_mod_to_token_stream,
+  },
   BindgenResult, CallbackArg, FnKind, FnSelf, NapiFn, NapiFnArgKind, TryToTokens,
 };
 
+/// The `f32` or `f64` of the argument type, which could be wrapped in `Option`
+fn float_type_of(ty: &syn::Type) -> Option<&Ident> {
+  if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+    let last = path.segments.last()?;
+    if last.ident == "f32" || last.ident == "f64" {
+      return Some(&last.ident);
+    }
+    if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+      if last.ident == "Option" {
+        if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+          return float_type_of(inner);
+        }
+      }
+    }
+  }
+  None
+}
+
 impl TryToTokens for NapiFn {
   fn try_to_tokens(&self, tokens: &mut TokenStream) -> BindgenResult<()> {
     let name_str = self.name.to_string();
@@ -28,14 +49,30 @@ impl TryToTokens for NapiFn {
       }
     } else {
       let call = if self.is_ret_result {
-        quote! { #receiver(#(#arg_names),*).await }
+        quote! { #receiver(#(#arg_names),*).await.map_err(Into::<napi::bindgen_prelude::Error>::into) }
       } else {
         quote! { Ok(#receiver(#(#arg_names),*).await) }
       };
-      quote! {
-        napi::bindgen_prelude::execute_tokio_future(env, async move { #call }, |env, #receiver_ret_name| {
-          #ret
-        })
+      let fut = if self.has_abort_signal() {
+        // the `Future` is dropped and the `Promise` is rejected once the signal is aborted
+        quote! { napi::bindgen_prelude::abortable(napi_abort_signal, async move { #call }) }
+      } else {
+        quote! { async move { #call } }
+      };
+      if self.fn_self.is_some() {
+        // the guard is dropped on the JavaScript thread after the `Promise` is settled
+        quote! {
+          napi::bindgen_prelude::execute_future(env, #fut, move |env, #receiver_ret_name| {
+            let _guard = napi_async_guard;
+            #ret
+          })
+        }
+      } else {
+        quote! {
+          napi::bindgen_prelude::execute_future(env, #fut, |env, #receiver_ret_name| {
+            #ret
+          })
+        }
       }
     };
 
@@ -45,7 +82,18 @@ impl TryToTokens for NapiFn {
       && self.kind != FnKind::Factory
     {
       quote! { #native_call }
-    } else if self.kind == FnKind::Constructor {
+    } else {
+      quote! {
+        napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
+          #(#arg_conversions)*
+          #native_call
+        })
+      }
+    };
+
+    let function_call = gen_catch_unwind(self.catch_unwind, function_call);
+
+    let call_from_factory_check = if self.kind == FnKind::Constructor {
       quote! {
         // constructor function is called from class `factory`
         // so we should skip the original `constructor` logic
@@ -53,18 +101,9 @@ impl TryToTokens for NapiFn {
         if inner.load(std::sync::atomic::Ordering::Relaxed) {
           return std::ptr::null_mut();
         }
-        napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
-          #(#arg_conversions)*
-          #native_call
-        })
       }
     } else {
-      quote! {
-        napi::bindgen_prelude::CallbackInfo::<#args_len>::new(env, cb, None).and_then(|mut cb| {
-          #(#arg_conversions)*
-          #native_call
-        })
-      }
+      quote! {}
     };
 
     (quote! {
@@ -76,6 +115,7 @@ impl TryToTokens for NapiFn {
         env: napi::bindgen_prelude::sys::napi_env,
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
+        #call_from_factory_check
         unsafe {
           #function_call.unwrap_or_else(|e| {
             napi::bindgen_prelude::JsError::from(e).throw_into(env);
@@ -114,9 +154,18 @@ impl NapiFn {
         }
         _ => {}
       };
+      if self.is_async && self.fn_self.is_some() {
+        // keep the instance alive while the future is borrowing it
+        let is_mut = matches!(self.fn_self, Some(FnSelf::MutRef));
+        let js_name = &self.js_name;
+        arg_conversions.push(quote! {
+          let napi_async_guard = napi::bindgen_prelude::AsyncMethodGuard::<#parent>::new(env, this_ptr, #is_mut, #js_name)?;
+        });
+      }
     }
 
     let mut skipped_arg_count = 0;
+    let mut abort_signal_cloned = false;
     self.args.iter().enumerate().for_each(|(i, arg)| {
       let i = i - skipped_arg_count;
       let ident = Ident::new(&format!("arg{}", i), Span::call_site());
@@ -126,6 +175,10 @@ impl NapiFn {
           if &path.ty.to_token_stream().to_string() == "Env" {
             args.push(quote! { napi::bindgen_prelude::Env::from(env) });
             skipped_arg_count += 1;
+          } else if arg.is_this() {
+            arg_conversions.push(self.gen_this_arg_conversion(path));
+            args.push(quote! { napi_this });
+            skipped_arg_count += 1;
           } else {
             if self.parent.is_some() {
               if let syn::Type::Path(path) = path.ty.as_ref() {
@@ -151,7 +204,24 @@ impl NapiFn {
                 }
               }
             }
-            arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path));
+            if arg.is_rest() {
+              let ty = &path.ty;
+              arg_conversions.push(quote! {
+                let #ident = <#ty>::from_napi_values(env, cb.get_rest_args(#i)?)?;
+              });
+            } else {
+              arg_conversions.push(self.gen_ty_arg_conversion(&ident, i, path, arg.default.as_ref()));
+              if self.is_async && !abort_signal_cloned {
+                if let Some(is_optional) = abort_signal_kind(&path.ty) {
+                  arg_conversions.push(if is_optional {
+                    quote! { let napi_abort_signal = #ident.clone(); }
+                  } else {
+                    quote! { let napi_abort_signal = Some(#ident.clone()); }
+                  });
+                  abort_signal_cloned = true;
+                }
+              }
+            }
             args.push(quote! { #ident });
           }
         }
@@ -165,11 +235,20 @@ impl NapiFn {
     (arg_conversions, args)
   }
 
+  fn has_abort_signal(&self) -> bool {
+    self.is_async
+      && self.args.iter().any(|arg| match &arg.kind {
+        NapiFnArgKind::PatType(path) => !arg.is_rest() && abort_signal_kind(&path.ty).is_some(),
+        _ => false,
+      })
+  }
+
   fn gen_ty_arg_conversion(
     &self,
     arg_name: &Ident,
     index: usize,
     path: &syn::PatType,
+    default: Option<&syn::Lit>,
   ) -> TokenStream {
     let ty = &*path.ty;
     match ty {
@@ -199,16 +278,71 @@ impl NapiFn {
           quote! {}
         };
 
-        quote! {
-          let #arg_name = {
-            #type_check
-            <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index))?
-          };
+        match default {
+          Some(default) => {
+            let default = match default {
+              syn::Lit::Str(_) => quote! { #default.into() },
+              // `#[napi(default = 2)]` on a float argument
+              syn::Lit::Int(int) if int.suffix().is_empty() => match float_type_of(ty) {
+                Some(float) => quote! { #default as #float },
+                None => quote! { #default },
+              },
+              _ => quote! { #default },
+            };
+            let default = if is_option_type(ty) {
+              quote! { Some(#default) }
+            } else {
+              default
+            };
+            quote! {
+              let #arg_name = {
+                let mut value_type = 0;
+                napi::bindgen_prelude::check_status!(
+                  napi::bindgen_prelude::sys::napi_typeof(env, cb.get_arg(#index), &mut value_type),
+                  "Failed to get the type of argument {}",
+                  #index,
+                )?;
+                // both `undefined` and `null` are replaced by the default value
+                if value_type == napi::bindgen_prelude::sys::ValueType::napi_undefined
+                  || value_type == napi::bindgen_prelude::sys::ValueType::napi_null
+                {
+                  #default
+                } else {
+                  #type_check
+                  <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index))?
+                }
+              };
+            }
+          }
+          None => quote! {
+            let #arg_name = {
+              #type_check
+              <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.get_arg(#index))?
+            };
+          },
         }
       }
     }
   }
 
+  fn gen_this_arg_conversion(&self, path: &syn::PatType) -> TokenStream {
+    let ty = &*path.ty;
+    let type_check = if self.strict {
+      quote! {
+        <#ty as napi::bindgen_prelude::ValidateNapiValue>::validate(env, cb.this())?;
+      }
+    } else {
+      quote! {}
+    };
+
+    quote! {
+      let napi_this = {
+        #type_check
+        <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb.this())?
+      };
+    }
+  }
+
   fn gen_cb_arg_conversion(&self, arg_name: &Ident, index: usize, cb: &CallbackArg) -> TokenStream {
     let mut inputs = vec![];
     let mut arg_conversions = vec![];
@@ -241,7 +375,8 @@ impl NapiFn {
 
         let mut ret_ptr = std::ptr::null_mut();
 
-        napi::bindgen_prelude::check_status!(
+        napi::bindgen_prelude::check_pending_exception!(
+          env,
           napi::bindgen_prelude::sys::napi_call_function(
             env,
             cb.this(),
@@ -280,29 +415,49 @@ impl NapiFn {
     if let Some(ty) = &self.ret {
       let ty_string = ty.into_token_stream().to_string();
       let is_return_self = ty_string == "& Self" || ty_string == "&mut Self";
-      if self.kind == FnKind::Constructor {
+      if self.kind == FnKind::Constructor || self.kind == FnKind::Factory {
+        let method = match (
+          &self.kind,
+          self.parent_is_generator,
+          self.parent_is_async_generator,
+        ) {
+          (FnKind::Constructor, true, _) => "construct_generator",
+          (FnKind::Constructor, _, true) => "construct_async_generator",
+          (FnKind::Constructor, _, _) => "construct",
+          (_, true, _) => "generator_factory",
+          (_, _, true) => "async_generator_factory",
+          _ => "factory",
+        };
+        let method = Ident::new(method, Span::call_site());
         if self.is_ret_result {
-          if self.parent_is_generator {
-            quote! { cb.construct_generator(#js_name, #ret?) }
-          } else {
-            quote! { cb.construct(#js_name, #ret?) }
-          }
-        } else if self.parent_is_generator {
-          quote! { cb.construct_generator(#js_name, #ret) }
+          quote! { cb.#method(#js_name, #ret.map_err(Into::<napi::bindgen_prelude::Error>::into)?) }
         } else {
-          quote! { cb.construct(#js_name, #ret) }
+          quote! { cb.#method(#js_name, #ret) }
         }
-      } else if self.kind == FnKind::Factory {
-        if self.is_ret_result {
-          if self.parent_is_generator {
-            quote! { cb.generator_factory(#js_name, #ret?) }
+      } else if is_impl_trait(ty, "Stream") || is_impl_trait(ty, "Iterator") {
+        // `impl Stream<Item = Result<T>>` is converted into `AsyncIterable<T>`, `impl Iterator<Item = T>` into `Iterable<T>`
+        let convert = |value: TokenStream| {
+          if is_impl_trait(ty, "Stream") {
+            quote! { napi::bindgen_prelude::create_stream_iterator(env, #value) }
           } else {
-            quote! { cb.factory(#js_name, #ret?) }
+            quote! {
+              napi::bindgen_prelude::ToNapiValue::to_napi_value(env, napi::bindgen_prelude::JsIterable::new(#value))
+            }
+          }
+        };
+        if self.is_ret_result && !self.is_async {
+          let convert_value = convert(quote! { value });
+          quote! {
+            match #ret {
+              Ok(value) => #convert_value,
+              Err(err) => {
+                napi::bindgen_prelude::JsError::from(Into::<napi::bindgen_prelude::Error>::into(err)).throw_into(env);
+                Ok(std::ptr::null_mut())
+              },
+            }
           }
-        } else if self.parent_is_generator {
-          quote! { cb.generator_factory(#js_name, #ret) }
         } else {
-          quote! { cb.factory(#js_name, #ret) }
+          convert(quote! { #ret })
         }
       } else if self.is_ret_result {
         if self.is_async {
@@ -310,13 +465,13 @@ impl NapiFn {
             <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #ret)
           }
         } else if is_return_self {
-          quote! { #ret.map(|_| cb.this) }
+          quote! { #ret.map(|_| cb.this).map_err(Into::into) }
         } else {
           quote! {
             match #ret {
               Ok(value) => napi::bindgen_prelude::ToNapiValue::to_napi_value(env, value),
               Err(err) => {
-                napi::bindgen_prelude::JsError::from(err).throw_into(env);
+                napi::bindgen_prelude::JsError::from(Into::<napi::bindgen_prelude::Error>::into(err)).throw_into(env);
                 Ok(std::ptr::null_mut())
               },
             }
@@ -339,6 +494,8 @@ impl NapiFn {
   fn gen_fn_register(&self) -> TokenStream {
     if self.parent.is_some() {
       quote! {}
+    } else if self.overload {
+      self.gen_fn_overload_register()
     } else {
       let name_str = self.name.to_string();
       let js_name = format!("{}\0", &self.js_name);
@@ -379,4 +536,149 @@ impl NapiFn {
       }
     }
   }
+
+  fn gen_fn_overload_register(&self) -> TokenStream {
+    let name_str = self.name.to_string();
+    let js_name = format!("{}\0", &self.js_name);
+    let module_register_name = get_register_ident(&name_str);
+    let intermediate_ident = get_intermediate_ident(&name_str);
+    let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let cb_name = Ident::new(&format!("{}_js_function", name_str), Span::call_site());
+    let validator_name = Ident::new(
+      &format!("__napi__validate__overload__{}", name_str),
+      Span::call_site(),
+    );
+
+    let mut checks = vec![];
+    for arg in self.args.iter() {
+      let index = checks.len();
+      match &arg.kind {
+        NapiFnArgKind::PatType(path) => {
+          let ty = &*path.ty;
+          if ty.to_token_stream().to_string() == "Env" || arg.is_this() {
+            continue;
+          }
+          checks.push(quote! {
+            <#ty as napi::bindgen_prelude::ValidateNapiValue>::is_valid(env, args[#index])
+          });
+        }
+        NapiFnArgKind::Callback(_) => {
+          checks.push(quote! {
+            <napi::JsFunction as napi::bindgen_prelude::ValidateNapiValue>::is_valid(env, args[#index])
+          });
+        }
+      }
+    }
+    let args_len = checks.len();
+    let signature = format!("{}({})", self.js_name, self.gen_overload_signature_args());
+
+    quote! {
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      unsafe fn #cb_name(env: napi::bindgen_prelude::sys::napi_env) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+        napi::__private::create_fn_overload(env, #js_mod_ident, #js_name)
+      }
+
+      #[allow(non_snake_case)]
+      #[allow(clippy::all)]
+      #[allow(unused_variables)]
+      unsafe fn #validator_name(
+        env: napi::bindgen_prelude::sys::napi_env,
+        args: &[napi::bindgen_prelude::sys::napi_value],
+      ) -> bool {
+        true #(&& #checks)*
+      }
+
+      #[allow(clippy::all)]
+      #[allow(non_snake_case)]
+      #[cfg(all(not(test), not(feature = "noop")))]
+      #[napi::bindgen_prelude::ctor]
+      fn #module_register_name() {
+        napi::__private::register_fn_overload(
+          #js_mod_ident,
+          #js_name,
+          #cb_name,
+          napi::__private::FnOverload {
+            callback: #intermediate_ident,
+            validator: #validator_name,
+            args_len: #args_len,
+            signature: #signature,
+            file: file!(),
+            line: line!(),
+            column: column!(),
+          },
+        );
+      }
+    }
+  }
+}
+
+impl NapiFn {
+  /// Arguments of the overload shown in the error message, in the same form as the `.d.ts` file
+  #[cfg(feature = "type-def")]
+  fn gen_overload_signature_args(&self) -> String {
+    self
+      .ts_args_type
+      .clone()
+      .unwrap_or_else(|| self.gen_ts_func_args())
+  }
+
+  /// Only the argument names are known without the TypeScript types
+  #[cfg(not(feature = "type-def"))]
+  fn gen_overload_signature_args(&self) -> String {
+    use convert_case::{Case, Casing};
+
+    self
+      .args
+      .iter()
+      .filter_map(|arg| match &arg.kind {
+        NapiFnArgKind::PatType(path) => {
+          if path.ty.to_token_stream().to_string() == "Env" || arg.is_this() {
+            return None;
+          }
+          Some(path.pat.to_token_stream().to_string().to_case(Case::Camel))
+        }
+        NapiFnArgKind::Callback(cb) => {
+          Some(cb.pat.to_token_stream().to_string().to_case(Case::Camel))
+        }
+      })
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+}
+
+fn is_impl_trait(ty: &syn::Type, trait_name: &str) -> bool {
+  match ty {
+    syn::Type::ImplTrait(impl_trait) => impl_trait.bounds.iter().any(|bound| match bound {
+      syn::TypeParamBound::Trait(t) => t
+        .path
+        .segments
+        .last()
+        .map_or(false, |segment| segment.ident == trait_name),
+      _ => false,
+    }),
+    _ => false,
+  }
+}
+
+/// `Some(true)` for `Option<AbortSignal>` and `Some(false)` for `AbortSignal`
+fn abort_signal_kind(ty: &syn::Type) -> Option<bool> {
+  let segment = match ty {
+    syn::Type::Path(path) => path.path.segments.last()?,
+    _ => return None,
+  };
+  if segment.ident == "AbortSignal" {
+    return Some(false);
+  }
+  if segment.ident == "Option" {
+    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+      if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+        return match abort_signal_kind(inner) {
+          Some(false) => Some(true),
+          _ => None,
+        };
+      }
+    }
+  }
+  None
 }
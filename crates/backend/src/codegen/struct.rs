@@ -5,15 +5,20 @@ use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
 
 use crate::{
-  codegen::{get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
+  codegen::{gen_catch_unwind, get_intermediate_ident, get_register_ident, js_mod_to_token_stream},
   BindgenResult, FnKind, NapiImpl, NapiStruct, NapiStructKind, TryToTokens,
 };
 
 static NAPI_IMPL_ID: AtomicU32 = AtomicU32::new(0);
 
 // Generate trait implementations for given Struct.
-fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> TokenStream {
+fn gen_napi_value_map_impl(
+  name: &Ident,
+  js_name: &str,
+  to_napi_val_impl: TokenStream,
+) -> TokenStream {
   let name_str = name.to_string();
+  let js_name_str = format!("{}\0", js_name);
   quote! {
     impl napi::bindgen_prelude::TypeName for #name {
       fn type_name() -> &'static str {
@@ -99,6 +104,24 @@ fn gen_napi_value_map_impl(name: &Ident, to_napi_val_impl: TokenStream) -> Token
       }
     }
 
+    impl napi::bindgen_prelude::ValidateNapiValue for &#name {
+      unsafe fn validate(
+        env: napi::bindgen_prelude::sys::napi_env,
+        napi_val: napi::bindgen_prelude::sys::napi_value
+      ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+        napi::__private::validate_class_instance(env, napi_val, #js_name_str)
+      }
+    }
+
+    impl napi::bindgen_prelude::ValidateNapiValue for &mut #name {
+      unsafe fn validate(
+        env: napi::bindgen_prelude::sys::napi_env,
+        napi_val: napi::bindgen_prelude::sys::napi_value
+      ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+        napi::__private::validate_class_instance(env, napi_val, #js_name_str)
+      }
+    }
+
     impl napi::NapiRaw for &#name {
       unsafe fn raw(&self) -> napi::sys::napi_value {
         unreachable!()
@@ -122,10 +145,12 @@ impl TryToTokens for NapiStruct {
     } else {
       self.gen_helper_mod()
     };
+    let upcast_impl = self.gen_upcast_impl();
 
     (quote! {
       #napi_value_map_impl
       #class_helper_mod
+      #upcast_impl
     })
     .to_tokens(tokens);
 
@@ -188,17 +213,25 @@ impl NapiStruct {
 
     let constructor = if self.implement_iterator {
       quote! { unsafe { cb.construct_generator(#js_name_str, #construct) } }
+    } else if self.implement_async_iterator {
+      quote! { unsafe { cb.construct_async_generator(#js_name_str, #construct) } }
     } else {
       quote! { unsafe { cb.construct(#js_name_str, #construct) } }
     };
+    let body = gen_catch_unwind(
+      self.catch_unwind,
+      quote! {
+        napi::bindgen_prelude::CallbackInfo::<#fields_len>::new(env, cb, None)
+          .and_then(|cb| #constructor)
+      },
+    );
 
     quote! {
       extern "C" fn constructor(
         env: napi::bindgen_prelude::sys::napi_env,
         cb: napi::bindgen_prelude::sys::napi_callback_info
       ) -> napi::bindgen_prelude::sys::napi_value {
-        napi::bindgen_prelude::CallbackInfo::<#fields_len>::new(env, cb, None)
-          .and_then(|cb| #constructor)
+        #body
           .unwrap_or_else(|e| {
             unsafe { napi::bindgen_prelude::JsError::from(e).throw_into(env) };
             std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
@@ -207,15 +240,42 @@ impl NapiStruct {
     }
   }
 
+  fn gen_upcast_impl(&self) -> TokenStream {
+    let extends = match &self.extends {
+      Some(extends) => extends,
+      None => return quote! {},
+    };
+    let name = &self.name;
+    let base = &extends.ty;
+    let member = &extends.member;
+
+    quote! {
+      impl AsRef<#base> for #name {
+        fn as_ref(&self) -> &#base {
+          &self.#member
+        }
+      }
+
+      impl AsMut<#base> for #name {
+        fn as_mut(&mut self) -> &mut #base {
+          &mut self.#member
+        }
+      }
+    }
+  }
+
   fn gen_napi_value_map_impl(&self) -> TokenStream {
     match self.kind {
       NapiStructKind::None => gen_napi_value_map_impl(
         &self.name,
+        &self.js_name,
         self.gen_to_napi_value_ctor_impl_for_non_default_constructor_struct(),
       ),
-      NapiStructKind::Constructor => {
-        gen_napi_value_map_impl(&self.name, self.gen_to_napi_value_ctor_impl())
-      }
+      NapiStructKind::Constructor => gen_napi_value_map_impl(
+        &self.name,
+        &self.js_name,
+        self.gen_to_napi_value_ctor_impl(),
+      ),
       NapiStructKind::Object => self.gen_to_napi_value_obj_impl(),
     }
   }
@@ -307,6 +367,11 @@ impl NapiStruct {
   }
 
   fn gen_iterator_property(&self, name: &Ident) -> TokenStream {
+    if self.implement_async_iterator {
+      return quote! {
+        napi::__private::create_async_iterator::<#name>(env, instance_value, wrapped_value);
+      };
+    }
     if !self.implement_iterator {
       return quote! {};
     }
@@ -395,6 +460,7 @@ impl NapiStruct {
     let mut obj_field_setters = vec![];
     let mut obj_field_getters = vec![];
     let mut field_destructions = vec![];
+    let mut obj_field_validators = vec![];
 
     for field in self.fields.iter() {
       let field_js_name = &field.js_name;
@@ -412,6 +478,16 @@ impl NapiStruct {
       } else {
         false
       };
+      if !is_optional_field {
+        obj_field_validators.push(quote! {
+          if obj.get::<_, napi::JsUnknown>(#field_js_name)?.is_none() {
+            return Err(napi::bindgen_prelude::Error::new(
+              napi::bindgen_prelude::Status::InvalidArg,
+              format!("Missing field `{}`", #field_js_name),
+            ));
+          }
+        });
+      }
       match &field.name {
         syn::Member::Named(ident) => {
           field_destructions.push(quote! { #ident });
@@ -508,6 +584,30 @@ impl NapiStruct {
           Ok(val)
         }
       }
+
+      impl napi::bindgen_prelude::ValidateNapiValue for #name {
+        fn type_of() -> Vec<napi::ValueType> {
+          vec![napi::ValueType::Object]
+        }
+
+        unsafe fn validate(
+          env: napi::bindgen_prelude::sys::napi_env,
+          napi_val: napi::bindgen_prelude::sys::napi_value
+        ) -> napi::bindgen_prelude::Result<napi::bindgen_prelude::sys::napi_value> {
+          let value_type = napi::bindgen_prelude::type_of!(env, napi_val)?;
+          if value_type != napi::ValueType::Object {
+            return Err(napi::bindgen_prelude::Error::new(
+              napi::bindgen_prelude::Status::InvalidArg,
+              format!("Expect value to be Object, but received {}", value_type),
+            ));
+          }
+          let obj = napi::bindgen_prelude::Object::from_napi_value(env, napi_val)?;
+
+          #(#obj_field_validators)*
+
+          Ok(std::ptr::null_mut())
+        }
+      }
     }
   }
 
@@ -527,6 +627,17 @@ impl NapiStruct {
       let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
 
       if field.getter {
+        let body = gen_catch_unwind(
+          self.catch_unwind,
+          quote! {
+            napi::bindgen_prelude::CallbackInfo::<0>::new(env, cb, Some(0))
+              .and_then(|mut cb| unsafe { cb.unwrap_borrow::<#struct_name>() })
+              .and_then(|obj| {
+                let val = obj.#field_ident.to_owned();
+                unsafe { <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, val) }
+              })
+          },
+        );
         getters_setters.push((
           field.js_name.clone(),
           quote! {
@@ -534,12 +645,7 @@ impl NapiStruct {
               env: napi::bindgen_prelude::sys::napi_env,
               cb: napi::bindgen_prelude::sys::napi_callback_info
             ) -> napi::bindgen_prelude::sys::napi_value {
-              napi::bindgen_prelude::CallbackInfo::<0>::new(env, cb, Some(0))
-                .and_then(|mut cb| unsafe { cb.unwrap_borrow::<#struct_name>() })
-                .and_then(|obj| {
-                  let val = obj.#field_ident.to_owned();
-                  unsafe { <#ty as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, val) }
-                })
+              #body
                 .unwrap_or_else(|e| {
                   unsafe { napi::bindgen_prelude::JsError::from(e).throw_into(env) };
                   std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
@@ -550,6 +656,22 @@ impl NapiStruct {
       }
 
       if field.setter {
+        let body = gen_catch_unwind(
+          self.catch_unwind,
+          quote! {
+            napi::bindgen_prelude::CallbackInfo::<1>::new(env, cb, Some(1))
+              .and_then(|mut cb_info| unsafe {
+                cb_info.unwrap_borrow_mut::<#struct_name>()
+                  .and_then(|obj| {
+                    <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb_info.get_arg(0))
+                      .and_then(move |val| {
+                        obj.#field_ident = val;
+                        <() as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, ())
+                      })
+                  })
+              })
+          },
+        );
         getters_setters.push((
           field.js_name.clone(),
           quote! {
@@ -557,17 +679,7 @@ impl NapiStruct {
               env: napi::bindgen_prelude::sys::napi_env,
               cb: napi::bindgen_prelude::sys::napi_callback_info
             ) -> napi::bindgen_prelude::sys::napi_value {
-              napi::bindgen_prelude::CallbackInfo::<1>::new(env, cb, Some(1))
-                .and_then(|mut cb_info| unsafe {
-                  cb_info.unwrap_borrow_mut::<#struct_name>()
-                    .and_then(|obj| {
-                      <#ty as napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, cb_info.get_arg(0))
-                        .and_then(move |val| {
-                          obj.#field_ident = val;
-                          <() as napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, ())
-                        })
-                    })
-                })
+              #body
                 .unwrap_or_else(|e| {
                   unsafe { napi::bindgen_prelude::JsError::from(e).throw_into(env) };
                   std::ptr::null_mut::<napi::bindgen_prelude::sys::napi_value__>()
@@ -620,6 +732,12 @@ impl NapiStruct {
       props.push(prop);
     }
     let js_mod_ident = js_mod_to_token_stream(self.js_mod.as_ref());
+    let register_parent = self.extends.as_ref().map(|extends| {
+      let parent_js_name = format!("{}\0", extends.js_name);
+      quote! {
+        napi::__private::register_class_parent(#js_name, #parent_js_name);
+      }
+    });
     quote! {
       #[allow(non_snake_case)]
       #[allow(clippy::all)]
@@ -627,6 +745,7 @@ impl NapiStruct {
       #[napi::bindgen_prelude::ctor]
       fn #struct_register_name() {
         napi::__private::register_class(#name_str, #js_mod_ident, #js_name, vec![#(#props),*]);
+        #register_parent
       }
     }
   }
@@ -42,6 +42,12 @@ macro_rules! napi_ast_impl {
 			fn to_type_def(&self) -> Option<TypeDef> {
 				match self.item {
           $( NapiItem::$v(ref ast) => ast.to_type_def() ),*
+        }
+			}
+
+			fn to_type_defs(&self) -> Vec<TypeDef> {
+				match self.item {
+          $( NapiItem::$v(ref ast) => ast.to_type_defs() ),*
         }
 			}
 		}
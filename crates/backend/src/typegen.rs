@@ -16,6 +16,7 @@ pub struct TypeDef {
   pub def: String,
   pub js_mod: Option<String>,
   pub js_doc: String,
+  pub extends: Option<String>,
 }
 
 thread_local! {
@@ -102,20 +103,31 @@ impl ToString for TypeDef {
     } else {
       "".to_owned()
     };
+    let extends = if let Some(extends) = &self.extends {
+      format!(", \"extends\": \"{}\"", extends)
+    } else {
+      "".to_owned()
+    };
     format!(
-      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}}}"#,
+      r#"{{"kind": "{}", "name": "{}", "js_doc": "{}", "def": "{}"{}{}{}}}"#,
       self.kind,
       self.name,
       escape_json(&self.js_doc),
       escape_json(&self.def),
       original_name,
       js_mod,
+      extends,
     )
   }
 }
 
 pub trait ToTypeDef {
   fn to_type_def(&self) -> Option<TypeDef>;
+
+  /// Some items, e.g. `#[napi(error)] enum`, are emitted as more than one TypeScript declaration.
+  fn to_type_defs(&self) -> Vec<TypeDef> {
+    self.to_type_def().into_iter().collect()
+  }
 }
 
 static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
@@ -174,6 +186,10 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("JsBuffer", "Buffer"),
     ("Buffer", "Buffer"),
     ("Vec", "Array<{}>"),
+    ("Rest", "Array<{}>"),
+    ("JsIterator", "Iterable<{}>"),
+    ("JsIterable", "Iterable<{}>"),
+    ("JsAsyncIterable", "AsyncIterable<{}>"),
     ("Result", "Error | {}"),
     ("Either", "{} | {}"),
     ("Either3", "{} | {} | {}"),
@@ -187,6 +203,7 @@ static KNOWN_TYPES: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     ("external", "object"),
     ("AbortSignal", "AbortSignal"),
     ("JsFunction", "(...args: any[]) => any"),
+    ("ProgressCallback", "(progress: {}) => void"),
     ("JsGlobal", "typeof global"),
     ("External", "ExternalObject<{}>"),
     ("unknown", "unknown"),
@@ -271,7 +288,7 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool, is_struct_field: bool) -> (S
               true,
             )
           });
-        } else if rust_ty == "AsyncTask" {
+        } else if rust_ty == "AsyncTask" || rust_ty == "AsyncProgressTask" {
           ts_ty = r#struct::TASK_STRUCTS.with(|t| {
             let (output_type, _) = args.first().unwrap().to_owned();
             if let Some(o) = t.borrow().get(&output_type) {
@@ -328,6 +345,35 @@ pub fn ty_to_ts_type(ty: &Type, is_return_ty: bool, is_struct_field: bool) -> (S
       ts_ty.unwrap_or_else(|| ("any".to_owned(), false))
     }
     Type::Group(g) => ty_to_ts_type(&g.elem, is_return_ty, is_struct_field),
+    Type::ImplTrait(impl_trait) => impl_trait
+      .bounds
+      .iter()
+      .find_map(|bound| match bound {
+        // impl Stream<Item = Result<T>> => AsyncIterable<T>
+        // impl Iterator<Item = T> => Iterable<T>
+        syn::TypeParamBound::Trait(t) => t.path.segments.last().and_then(|segment| {
+          let ts_type = if segment.ident == "Stream" {
+            "AsyncIterable"
+          } else if segment.ident == "Iterator" {
+            "Iterable"
+          } else {
+            return None;
+          };
+          if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+            arguments.args.iter().find_map(|arg| match arg {
+              syn::GenericArgument::Binding(binding) if binding.ident == "Item" => Some((
+                format!("{}<{}>", ts_type, ty_to_ts_type(&binding.ty, true, false).0),
+                false,
+              )),
+              _ => None,
+            })
+          } else {
+            None
+          }
+        }),
+        _ => None,
+      })
+      .unwrap_or_else(|| ("any".to_owned(), false)),
     _ => ("any".to_owned(), false),
   }
 }
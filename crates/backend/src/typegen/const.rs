@@ -20,6 +20,7 @@ impl ToTypeDef for NapiConst {
         ty_to_ts_type(&self.type_name, false, false).0
       ),
       js_mod: self.js_mod.to_owned(),
+      extends: None,
       js_doc: js_doc_from_comments(&self.comments),
     })
   }
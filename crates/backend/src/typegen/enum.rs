@@ -1,5 +1,5 @@
 use super::{add_alias, ToTypeDef, TypeDef};
-use crate::{js_doc_from_comments, NapiEnum};
+use crate::{js_doc_from_comments, ty_to_ts_type, NapiEnum, NapiEnumKind, NapiStructField};
 
 impl ToTypeDef for NapiEnum {
   fn to_type_def(&self) -> Option<TypeDef> {
@@ -9,14 +9,93 @@ impl ToTypeDef for NapiEnum {
 
     add_alias(self.name.to_string(), self.js_name.to_string());
 
-    Some(TypeDef {
-      kind: "enum".to_owned(),
-      name: self.js_name.to_owned(),
-      original_name: Some(self.name.to_string()),
-      def: self.gen_ts_variants(),
-      js_doc: js_doc_from_comments(&self.comments),
-      js_mod: self.js_mod.to_owned(),
-    })
+    match self.kind {
+      NapiEnumKind::Numeric => Some(TypeDef {
+        kind: "enum".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        def: self.gen_ts_variants(),
+        js_doc: js_doc_from_comments(&self.comments),
+        js_mod: self.js_mod.to_owned(),
+        extends: None,
+      }),
+      NapiEnumKind::String => Some(TypeDef {
+        kind: "type".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        def: self.gen_ts_string_literals(),
+        js_doc: js_doc_from_comments(&self.comments),
+        js_mod: self.js_mod.to_owned(),
+        extends: None,
+      }),
+      NapiEnumKind::Tagged {
+        ref tag,
+        ref content,
+      } => Some(TypeDef {
+        kind: "type".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        def: self.gen_ts_tagged_union(tag, content.as_deref()),
+        js_doc: js_doc_from_comments(&self.comments),
+        js_mod: self.js_mod.to_owned(),
+        extends: None,
+      }),
+      NapiEnumKind::Error => Some(TypeDef {
+        kind: "struct".to_owned(),
+        name: self.js_name.to_owned(),
+        original_name: Some(self.name.to_string()),
+        def: "".to_owned(),
+        js_doc: js_doc_from_comments(&self.comments),
+        js_mod: self.js_mod.to_owned(),
+        extends: Some("Error".to_owned()),
+      }),
+    }
+  }
+
+  fn to_type_defs(&self) -> Vec<TypeDef> {
+    let base = match self.to_type_def() {
+      Some(base) => base,
+      None => return vec![],
+    };
+    if self.kind != NapiEnumKind::Error {
+      return vec![base];
+    }
+
+    // the variant classes are the static properties of the enum class,
+    // declared in the namespace merged with it, e.g. `ConfigError.NotFound`
+    let variants_mod = match &self.js_mod {
+      Some(js_mod) => format!("{}.{}", js_mod, self.js_name),
+      None => self.js_name.to_owned(),
+    };
+    let mut type_defs = vec![base];
+    type_defs.extend(self.variants.iter().map(|v| {
+      TypeDef {
+        kind: "struct".to_owned(),
+        name: v.js_name.to_owned(),
+        original_name: None,
+        def: v
+          .fields
+          .iter()
+          .filter(|f| f.getter && !f.skip_typescript)
+          .map(|f| {
+            let (ty, is_optional) = ty_to_ts_type(&f.ty, false, true);
+            let ty = f.ts_type.as_ref().map(|ty| ty.to_string()).unwrap_or(ty);
+            format!(
+              "{}{}{}: {}",
+              js_doc_from_comments(&f.comments),
+              f.js_name,
+              if is_optional { "?" } else { "" },
+              ty
+            )
+          })
+          .collect::<Vec<_>>()
+          .join("\\n"),
+        js_doc: js_doc_from_comments(&v.comments),
+        js_mod: Some(variants_mod.clone()),
+        extends: Some(self.js_name.to_owned()),
+      }
+    }));
+    type_defs
   }
 }
 
@@ -29,11 +108,91 @@ impl NapiEnum {
         format!(
           "{}{} = {}",
           js_doc_from_comments(&v.comments),
-          v.name,
+          v.js_name,
           v.val,
         )
       })
       .collect::<Vec<_>>()
       .join(",\n ")
   }
+
+  fn gen_ts_string_literals(&self) -> String {
+    if self.variants.is_empty() {
+      return "never".to_owned();
+    }
+    self
+      .variants
+      .iter()
+      .map(|v| format!("'{}'", v.js_name))
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
+
+  fn gen_ts_tagged_union(&self, tag: &str, content: Option<&str>) -> String {
+    if self.variants.is_empty() {
+      return "never".to_owned();
+    }
+    self
+      .variants
+      .iter()
+      .map(|v| {
+        let fields = v.fields.iter().filter(|f| f.getter).collect::<Vec<_>>();
+        let is_tuple = matches!(
+          fields.first(),
+          Some(NapiStructField {
+            name: syn::Member::Unnamed(_),
+            ..
+          })
+        );
+        let field_ts_type = |f: &NapiStructField, is_struct_field: bool| {
+          let (ty, is_optional) = ty_to_ts_type(&f.ty, false, is_struct_field);
+          (
+            f.ts_type.as_ref().map(|ty| ty.to_string()).unwrap_or(ty),
+            is_optional,
+          )
+        };
+        let object_fields = fields
+          .iter()
+          .filter(|f| !f.skip_typescript)
+          .map(|f| {
+            let (ty, is_optional) = field_ts_type(f, true);
+            format!(
+              "{}{}: {}",
+              f.js_name,
+              if is_optional { "?" } else { "" },
+              ty
+            )
+          })
+          .collect::<Vec<_>>();
+
+        let mut members = vec![format!("{}: '{}'", tag, v.js_name)];
+        match content {
+          Some(content) if is_tuple && fields.len() == 1 => {
+            let (ty, is_optional) = field_ts_type(fields[0], true);
+            members.push(format!(
+              "{}{}: {}",
+              content,
+              if is_optional { "?" } else { "" },
+              ty
+            ));
+          }
+          Some(content) if is_tuple => members.push(format!(
+            "{}: [{}]",
+            content,
+            fields
+              .iter()
+              .map(|f| field_ts_type(f, false).0)
+              .collect::<Vec<_>>()
+              .join(", ")
+          )),
+          Some(content) if !fields.is_empty() => {
+            members.push(format!("{}: {{ {} }}", content, object_fields.join(", ")))
+          }
+          _ => members.extend(object_fields),
+        }
+        format!("{{ {} }}", members.join(", "))
+      })
+      .collect::<Vec<_>>()
+      .join(" | ")
+  }
 }
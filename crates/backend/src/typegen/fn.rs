@@ -10,6 +10,8 @@ struct FnArg {
   arg: String,
   ts_type: String,
   is_optional: bool,
+  is_rest: bool,
+  default: Option<String>,
 }
 
 struct FnArgList {
@@ -27,8 +29,13 @@ impl Display for FnArgList {
         && self
           .last_required
           .map_or(true, |last_required| i > last_required);
-      if is_optional {
+      if arg.is_rest {
+        write!(f, "...{}: {}", arg.arg, arg.ts_type)?;
+      } else if is_optional {
         write!(f, "{}?: {}", arg.arg, arg.ts_type)?;
+      } else if arg.default.is_some() {
+        // followed by the required arguments, pass `undefined` to use the default value
+        write!(f, "{}: {} | undefined", arg.arg, arg.ts_type)?;
       } else {
         write!(f, "{}: {}", arg.arg, arg.ts_type)?;
       }
@@ -43,7 +50,7 @@ impl FromIterator<FnArg> for FnArgList {
     let last_required = args
       .iter()
       .enumerate()
-      .rfind(|(_, arg)| !arg.is_optional)
+      .rfind(|(_, arg)| !arg.is_optional && !arg.is_rest)
       .map(|(i, _)| i);
     FnArgList {
       args,
@@ -84,7 +91,8 @@ impl ToTypeDef for NapiFn {
       original_name: None,
       def,
       js_mod: self.js_mod.to_owned(),
-      js_doc: js_doc_from_comments(&self.comments),
+      extends: None,
+      js_doc: self.gen_ts_js_doc(),
     })
   }
 }
@@ -102,6 +110,8 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
           arg: format!("arg{}", i),
           ts_type,
           is_optional,
+          is_rest: false,
+          default: None,
         }
       })
       .collect::<FnArgList>(),
@@ -112,60 +122,96 @@ fn gen_callback_type(callback: &CallbackArg) -> String {
   )
 }
 
+fn lit_to_js_value(lit: &syn::Lit) -> String {
+  match lit {
+    syn::Lit::Str(s) => format!("{:?}", s.value()),
+    syn::Lit::Int(i) => i.base10_digits().to_owned(),
+    syn::Lit::Float(f) => f.base10_digits().to_owned(),
+    syn::Lit::Bool(b) => b.value.to_string(),
+    _ => lit.to_token_stream().to_string(),
+  }
+}
+
 impl NapiFn {
-  fn gen_ts_func_args(&self) -> String {
-    format!(
-      "{}",
-      self
-        .args
-        .iter()
-        .filter_map(|arg| match &arg.kind {
-          crate::NapiFnArgKind::PatType(path) => {
-            let ty_string = path.ty.to_token_stream().to_string();
-            if ty_string == "Env" {
-              return None;
-            }
-            if let syn::Type::Path(path) = path.ty.as_ref() {
-              if let Some(PathSegment {
-                ident,
-                arguments: PathArguments::AngleBracketed(_),
-              }) = path.path.segments.last()
-              {
-                if ident == "Reference" || ident == "WeakReference" {
-                  return None;
-                }
+  /// The doc comments, followed by the `@param` tags of the arguments with default values
+  pub(crate) fn gen_ts_js_doc(&self) -> String {
+    if self.ts_args_type.is_some() {
+      return js_doc_from_comments(&self.comments);
+    }
+    let mut comments = self.comments.clone();
+    comments.extend(self.gen_ts_func_arg_list().args.iter().filter_map(|arg| {
+      arg
+        .default
+        .as_ref()
+        .map(|default| format!(" @param [{}={}]", arg.arg, default))
+    }));
+    js_doc_from_comments(&comments)
+  }
+
+  pub(crate) fn gen_ts_func_args(&self) -> String {
+    self.gen_ts_func_arg_list().to_string()
+  }
+
+  fn gen_ts_func_arg_list(&self) -> FnArgList {
+    self
+      .args
+      .iter()
+      .filter_map(|arg| match &arg.kind {
+        crate::NapiFnArgKind::PatType(path) => {
+          let ty_string = path.ty.to_token_stream().to_string();
+          if ty_string == "Env" || arg.is_this() {
+            return None;
+          }
+          if let syn::Type::Path(path) = path.ty.as_ref() {
+            if let Some(PathSegment {
+              ident,
+              arguments: PathArguments::AngleBracketed(_),
+            }) = path.path.segments.last()
+            {
+              if ident == "Reference" || ident == "WeakReference" {
+                return None;
               }
             }
+          }
 
-            let mut path = path.clone();
-            // remove mutability from PatIdent
-            if let Pat::Ident(i) = path.pat.as_mut() {
-              i.mutability = None;
-            }
+          let mut path = path.clone();
+          // remove mutability from PatIdent
+          if let Pat::Ident(i) = path.pat.as_mut() {
+            i.mutability = None;
+          }
 
-            let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false, false);
-            let ts_type = arg.use_overridden_type_or(|| ts_type);
-            let arg = path.pat.to_token_stream().to_string().to_case(Case::Camel);
+          let (ts_type, is_optional) = ty_to_ts_type(&path.ty, false, false);
+          let default = arg.default.as_ref().map(lit_to_js_value);
+          let ts_type = arg.use_overridden_type_or(|| match default {
+            // `null` is replaced by the default value too
+            Some(_) if !is_optional => format!("{} | null", ts_type),
+            _ => ts_type,
+          });
+          let is_rest = arg.is_rest();
+          let arg = path.pat.to_token_stream().to_string().to_case(Case::Camel);
 
-            Some(FnArg {
-              arg,
-              ts_type,
-              is_optional,
-            })
-          }
-          crate::NapiFnArgKind::Callback(cb) => {
-            let ts_type = arg.use_overridden_type_or(|| gen_callback_type(cb));
-            let arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
-
-            Some(FnArg {
-              arg,
-              ts_type,
-              is_optional: false,
-            })
-          }
-        })
-        .collect::<FnArgList>()
-    )
+          Some(FnArg {
+            arg,
+            ts_type,
+            is_optional: is_optional || default.is_some(),
+            is_rest,
+            default,
+          })
+        }
+        crate::NapiFnArgKind::Callback(cb) => {
+          let ts_type = arg.use_overridden_type_or(|| gen_callback_type(cb));
+          let arg = cb.pat.to_token_stream().to_string().to_case(Case::Camel);
+
+          Some(FnArg {
+            arg,
+            ts_type,
+            is_optional: false,
+            is_rest: false,
+            default: None,
+          })
+        }
+      })
+      .collect::<FnArgList>()
   }
 
   fn gen_ts_func_prefix(&self) -> &'static str {
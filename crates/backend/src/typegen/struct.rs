@@ -27,6 +27,7 @@ impl ToTypeDef for NapiStruct {
       original_name: Some(self.name.to_string()),
       def: self.gen_ts_class(),
       js_mod: self.js_mod.to_owned(),
+      extends: self.extends.as_ref().map(|e| e.js_name.to_owned()),
       js_doc: js_doc_from_comments(&self.comments),
     })
   }
@@ -58,13 +59,23 @@ impl ToTypeDef for NapiImpl {
         kind: "impl".to_owned(),
         name: self.js_name.to_owned(),
         original_name: None,
-        def: format!(
-          "[Symbol.iterator](): Iterator<{}, {}, {}>",
-          ty_to_ts_type(output_type, false, true).0,
-          return_type,
-          next_type,
-        ),
+        def: if self.is_async_iterator {
+          format!(
+            "[Symbol.asyncIterator](): AsyncGenerator<{}, {}, {}>",
+            ty_to_ts_type(output_type, false, true).0,
+            return_type,
+            next_type,
+          )
+        } else {
+          format!(
+            "[Symbol.iterator](): Iterator<{}, {}, {}>",
+            ty_to_ts_type(output_type, false, true).0,
+            return_type,
+            next_type,
+          )
+        },
         js_mod: self.js_mod.to_owned(),
+        extends: None,
         js_doc: "".to_string(),
       })
     } else {
@@ -81,7 +92,7 @@ impl ToTypeDef for NapiImpl {
             } else {
               Some(format!(
                 "{}{}",
-                js_doc_from_comments(&f.comments),
+                f.gen_ts_js_doc(),
                 f.to_type_def()
                   .map_or(String::default(), |type_def| type_def.def)
               ))
@@ -90,6 +101,7 @@ impl ToTypeDef for NapiImpl {
           .collect::<Vec<_>>()
           .join("\\n"),
         js_mod: self.js_mod.to_owned(),
+        extends: None,
         js_doc: "".to_string(),
       })
     }
@@ -106,7 +106,7 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
           napi.try_to_tokens(&mut tokens)?;
           #[cfg(feature = "type-def")]
           if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-            if let Err(e) = output_type_def(type_def_file, napi.to_type_def()) {
+            if let Err(e) = output_type_def(type_def_file, napi.to_type_defs()) {
               println!("Failed to write type def file: {:?}", e);
             };
           }
@@ -132,7 +132,7 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
 
     #[cfg(feature = "type-def")]
     if let Ok(type_def_file) = env::var("TYPE_DEF_TMP_PATH") {
-      if let Err(e) = output_type_def(type_def_file, napi.to_type_def()) {
+      if let Err(e) = output_type_def(type_def_file, napi.to_type_defs()) {
         println!("Failed to write type def file: {:?}", e);
       };
     }
@@ -141,16 +141,19 @@ fn expand(attr: TokenStream, input: TokenStream) -> BindgenResult<TokenStream> {
 }
 
 #[cfg(all(feature = "type-def", not(feature = "noop")))]
-fn output_type_def(type_def_file: String, type_def: Option<TypeDef>) -> IOResult<()> {
-  if type_def.is_some() {
+fn output_type_def(type_def_file: String, type_defs: Vec<TypeDef>) -> IOResult<()> {
+  if !type_defs.is_empty() {
     let file = fs::OpenOptions::new()
       .append(true)
       .create(true)
       .open(type_def_file)?;
 
     let mut writer = BufWriter::<fs::File>::new(file);
-    writer.write_all(type_def.unwrap().to_string().as_bytes())?;
-    writer.write_all("\n".as_bytes())
+    for type_def in type_defs {
+      writer.write_all(type_def.to_string().as_bytes())?;
+      writer.write_all("\n".as_bytes())?;
+    }
+    writer.flush()
   } else {
     IOResult::Ok(())
   }
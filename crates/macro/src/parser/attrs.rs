@@ -53,10 +53,19 @@ macro_rules! attrgen {
       (object, Object(Span)),
       (namespace, Namespace(Span, String, Span)),
       (iterator, Iterator(Span)),
+      (async_iterator, AsyncIterator(Span)),
       (ts_args_type, TsArgsType(Span, String, Span)),
       (ts_return_type, TsReturnType(Span, String, Span)),
       (ts_type, TsType(Span, String, Span)),
       (ts_generic_types, TsGenericTypes(Span, String, Span)),
+      (abort_on_panic, AbortOnPanic(Span)),
+      (error, Error(Span)),
+      (string_enum, StringEnum(Span)),
+      (rename_all, RenameAll(Span, String, Span)),
+      (tag, Tag(Span, String, Span)),
+      (content, Content(Span, String, Span)),
+      (extends, Extends(Span, syn::Path)),
+      (overload, Overload(Span)),
 
       // impl later
       // (inspectable, Inspectable(Span)),
@@ -245,6 +254,16 @@ pub fn record_struct(ident: &Ident, js_name: String, opts: &BindgenAttrs) {
   });
 }
 
+pub fn get_recorded_struct_js_name(ident: &Ident) -> Option<String> {
+  STRUCTS.with(|state| {
+    state
+      .parsed
+      .borrow()
+      .get(&ident.to_string())
+      .map(|parsed| parsed.js_name.clone())
+  })
+}
+
 pub fn check_recorded_struct_for_impl(ident: &Ident, opts: &BindgenAttrs) -> BindgenResult<String> {
   STRUCTS.with(|state| {
     let struct_name = ident.to_string();
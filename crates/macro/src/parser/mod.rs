@@ -9,9 +9,9 @@ use attrs::{BindgenAttr, BindgenAttrs};
 
 use convert_case::{Case, Casing};
 use napi_derive_backend::{
-  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiConst, NapiEnum,
+  BindgenResult, CallbackArg, Diagnostic, FnKind, FnSelf, Napi, NapiConst, NapiEnum, NapiEnumKind,
   NapiEnumVariant, NapiFn, NapiFnArg, NapiFnArgKind, NapiImpl, NapiItem, NapiStruct,
-  NapiStructField, NapiStructKind,
+  NapiStructExtends, NapiStructField, NapiStructKind,
 };
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::ToTokens;
@@ -19,10 +19,13 @@ use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream, Result as SynResult};
 use syn::{Attribute, Meta, NestedMeta, PatType, PathSegment, Signature, Type, Visibility};
 
-use crate::parser::attrs::{check_recorded_struct_for_impl, record_struct};
+use crate::parser::attrs::{
+  check_recorded_struct_for_impl, get_recorded_struct_js_name, record_struct,
+};
 
 thread_local! {
   static GENERATOR_STRUCT: RefCell<HashMap<String, bool>> = Default::default();
+  static ASYNC_GENERATOR_STRUCT: RefCell<HashMap<String, bool>> = Default::default();
 }
 
 struct AnyIdent(Ident);
@@ -158,6 +161,8 @@ pub trait ParseNapi {
 /// This function does a few things:
 /// - parses the tokens for the given argument `p` to find the `#[napi(ts_arg_type = "MyType")]`
 ///   attribute and return the manually overridden type.
+/// - parses the `#[napi(default = 10)]` attribute and return the literal used when the argument is omitted,
+///   `undefined` or `null`.
 /// - If both the `ts_args_type` override and the `ts_arg_type` override are present, bail
 ///   since it should only allow one at a time.
 /// - Bails if it finds the `#[napi...]` attribute but it has the wrong data.
@@ -166,47 +171,64 @@ pub trait ParseNapi {
 ///    turns into
 ///   `pub fn add(u: u32, f: String)`
 ///    otherwise it won't compile
-fn find_ts_arg_type_and_remove_attribute(
+fn find_arg_attrs_and_remove_attribute(
   p: &mut PatType,
   ts_args_type: Option<&(&str, Span)>,
-) -> BindgenResult<Option<String>> {
+) -> BindgenResult<(Option<String>, Option<syn::Lit>)> {
+  let mut ts_arg_type = None;
+  let mut default = None;
+  let mut napi_attrs = vec![];
+
   for (idx, attr) in p.attrs.iter().enumerate() {
     if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
       if meta_list.path.get_ident() != Some(&format_ident!("napi")) {
         // If this attribute is not for `napi` ignore it.
         continue;
       }
+      napi_attrs.push(idx);
 
-      if let Some((ts_args_type, _)) = ts_args_type {
-        bail_span!(
-          meta_list,
-          "Found a 'ts_args_type'=\"{}\" override. Cannot use 'ts_arg_type' at the same time since they are mutually exclusive.",
-          ts_args_type
-        );
-      }
-
-      let nested = meta_list.nested.first();
-
-      let nm = if let Some(NestedMeta::Meta(Meta::NameValue(nm))) = nested {
-        nm
-      } else {
-        bail_span!(meta_list.nested, "Expected Name Value");
-      };
-
-      if Some(&format_ident!("ts_arg_type")) != nm.path.get_ident() {
-        bail_span!(nm.path, "Did not find 'ts_arg_type'");
-      }
+      for nested in meta_list.nested.iter() {
+        let nm = if let NestedMeta::Meta(Meta::NameValue(nm)) = nested {
+          nm
+        } else {
+          bail_span!(meta_list.nested, "Expected Name Value");
+        };
 
-      if let syn::Lit::Str(lit) = &nm.lit {
-        p.attrs.remove(idx);
-        return Ok(Some(lit.value()));
-      } else {
-        bail_span!(nm.lit, "Expected a string literal");
+        if Some(&format_ident!("ts_arg_type")) == nm.path.get_ident() {
+          if let Some((ts_args_type, _)) = ts_args_type {
+            bail_span!(
+              meta_list,
+              "Found a 'ts_args_type'=\"{}\" override. Cannot use 'ts_arg_type' at the same time since they are mutually exclusive.",
+              ts_args_type
+            );
+          }
+          if let syn::Lit::Str(lit) = &nm.lit {
+            ts_arg_type = Some(lit.value());
+          } else {
+            bail_span!(nm.lit, "Expected a string literal");
+          }
+        } else if Some(&format_ident!("default")) == nm.path.get_ident() {
+          match &nm.lit {
+            syn::Lit::Str(_) | syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Bool(_) => {
+              default = Some(nm.lit.clone());
+            }
+            _ => bail_span!(
+              nm.lit,
+              "Expected a string, number or boolean literal as the default value"
+            ),
+          }
+        } else {
+          bail_span!(nm.path, "Did not find 'ts_arg_type' or 'default'");
+        }
       }
     }
   }
 
-  Ok(None)
+  for idx in napi_attrs.into_iter().rev() {
+    p.attrs.remove(idx);
+  }
+
+  Ok((ts_arg_type, default))
 }
 
 fn get_ty(mut ty: &syn::Type) -> &syn::Type {
@@ -540,14 +562,22 @@ fn napi_fn_from_decl(
     .iter_mut()
     .filter_map(|arg| match arg {
       syn::FnArg::Typed(ref mut p) => {
-        let ts_arg_type = find_ts_arg_type_and_remove_attribute(p, opts.ts_args_type().as_ref())
-          .unwrap_or_else(|e| {
-            errors.push(e);
-            None
-          });
+        let (ts_arg_type, default) =
+          find_arg_attrs_and_remove_attribute(p, opts.ts_args_type().as_ref()).unwrap_or_else(
+            |e| {
+              errors.push(e);
+              (None, None)
+            },
+          );
 
         let ty_str = p.ty.to_token_stream().to_string();
         if let Some(path_arguments) = callback_traits.get(&ty_str) {
+          if let Some(default) = &default {
+            errors.push(err_span!(
+              default,
+              "#[napi(default)] is not supported on callback arguments"
+            ));
+          }
           match extract_callback_trait_types(path_arguments) {
             Ok((fn_args, fn_ret)) => Some(NapiFnArg {
               kind: NapiFnArgKind::Callback(Box::new(CallbackArg {
@@ -556,6 +586,7 @@ fn napi_fn_from_decl(
                 ret: fn_ret,
               })),
               ts_arg_type,
+              default: None,
             }),
             Err(e) => {
               errors.push(e);
@@ -563,11 +594,18 @@ fn napi_fn_from_decl(
             }
           }
         } else {
+          if let (Some(default), syn::Type::Reference(_)) = (&default, p.ty.as_ref()) {
+            errors.push(err_span!(
+              default,
+              "#[napi(default)] is not supported on reference arguments"
+            ));
+          }
           let ty = replace_self(p.ty.as_ref().clone(), parent);
           p.ty = Box::new(ty);
           Some(NapiFnArg {
             kind: NapiFnArgKind::PatType(Box::new(p.clone())),
             ts_arg_type,
+            default,
           })
         }
       }
@@ -604,6 +642,36 @@ fn napi_fn_from_decl(
     }
   };
 
+  if let (Some(span), Some(_)) = (opts.overload(), parent) {
+    errors.push(Diagnostic::span_error(
+      *span,
+      "#[napi(overload)] is only supported on standalone functions",
+    ));
+  }
+
+  for (i, arg) in args.iter().enumerate() {
+    if let (true, NapiFnArgKind::PatType(p)) = (arg.is_rest(), &arg.kind) {
+      if i != args.len() - 1 {
+        errors.push(err_span!(
+          p.ty,
+          "Rest<T> must be the last argument of the function"
+        ));
+      }
+      if let Some(default) = &arg.default {
+        errors.push(err_span!(
+          default,
+          "#[napi(default)] is not supported on Rest<T> arguments"
+        ));
+      }
+      if let Some(span) = opts.overload() {
+        errors.push(Diagnostic::span_error(
+          *span,
+          "Rest<T> arguments are not supported in #[napi(overload)] functions",
+        ));
+      }
+    }
+  }
+
   Diagnostic::from_vec(errors).map(|_| {
     let js_name = if let Some(prop_name) = opts.getter() {
       opts.js_name().map_or_else(
@@ -643,18 +711,18 @@ fn napi_fn_from_decl(
     };
 
     let namespace = opts.namespace().map(|(m, _)| m.to_owned());
-    let parent_is_generator = if let Some(p) = parent {
-      GENERATOR_STRUCT.with(|inner| {
-        let inner = inner.borrow();
-        let key = namespace
-          .as_ref()
-          .map(|n| format!("{}::{}", n, p))
-          .unwrap_or_else(|| p.to_string());
-        *inner.get(&key).unwrap_or(&false)
-      })
-    } else {
-      false
-    };
+    let parent_key = parent.map(|p| {
+      namespace
+        .as_ref()
+        .map(|n| format!("{}::{}", n, p))
+        .unwrap_or_else(|| p.to_string())
+    });
+    let parent_is_generator = parent_key.as_ref().map_or(false, |key| {
+      GENERATOR_STRUCT.with(|inner| *inner.borrow().get(key).unwrap_or(&false))
+    });
+    let parent_is_async_generator = parent_key.as_ref().map_or(false, |key| {
+      ASYNC_GENERATOR_STRUCT.with(|inner| *inner.borrow().get(key).unwrap_or(&false))
+    });
 
     NapiFn {
       name: ident,
@@ -676,6 +744,9 @@ fn napi_fn_from_decl(
       ts_return_type: opts.ts_return_type().map(|(m, _)| m.to_owned()),
       skip_typescript: opts.skip_typescript().is_some(),
       parent_is_generator,
+      parent_is_async_generator,
+      catch_unwind: opts.abort_on_panic().is_none(),
+      overload: opts.overload().is_some(),
     }
   })
 }
@@ -815,6 +886,29 @@ impl ConvertToAST for syn::ItemFn {
   }
 }
 
+/// Whether the struct is already `#[repr(C)]`, the other representations conflicting with it are rejected
+fn has_repr_c(attrs: &[Attribute]) -> BindgenResult<bool> {
+  let mut has_repr_c = false;
+  for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+    if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+      for nested in meta_list.nested.iter() {
+        if let NestedMeta::Meta(Meta::Path(path)) = nested {
+          if path.is_ident("C") {
+            has_repr_c = true;
+          } else if path.is_ident("Rust") || path.is_ident("transparent") {
+            bail_span!(
+              nested,
+              "#[napi(extends)] requires the struct to be #[repr(C)], #[repr({})] is not allowed",
+              path.to_token_stream(),
+            );
+          }
+        }
+      }
+    }
+  }
+  Ok(has_repr_c)
+}
+
 impl ConvertToAST for syn::ItemStruct {
   fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
     let mut errors = vec![];
@@ -835,7 +929,64 @@ impl ConvertToAST for syn::ItemStruct {
       NapiStructKind::None
     };
 
+    let extends = match opts.extends() {
+      Some(base) => {
+        if struct_kind != NapiStructKind::None {
+          bail_span!(
+            base,
+            "#[napi(extends)] could not be used with #[napi(constructor)] or #[napi(object)]"
+          );
+        }
+        let member = match self.fields.iter().next() {
+          Some(field)
+            if field.ty.to_token_stream().to_string() == base.to_token_stream().to_string() =>
+          {
+            field
+              .ident
+              .clone()
+              .map_or_else(|| syn::Member::Unnamed(0.into()), syn::Member::Named)
+          }
+          _ => bail_span!(
+            self.ident,
+            "the first field of the struct with #[napi(extends = {})] must be the `{}` itself",
+            base.to_token_stream(),
+            base.to_token_stream(),
+          ),
+        };
+        let base_ident = extract_path_ident(base)?;
+        // The `Base` must be placed at the start of the struct,
+        // so that the wrapped `*mut Self` could be used as `*mut Base` in the `Base` methods
+        if !has_repr_c(&self.attrs)? {
+          self.attrs.push(Attribute {
+            pound_token: Default::default(),
+            style: syn::AttrStyle::Outer,
+            bracket_token: Default::default(),
+            path: syn::parse_quote! { repr },
+            tokens: quote! { (C) },
+          });
+        }
+        let base_js_name = match get_recorded_struct_js_name(&base_ident) {
+          Some(js_name) => js_name,
+          None => bail_span!(
+            base,
+            "the base class `{}` of #[napi(extends)] must be a #[napi] struct defined before `{}`",
+            base_ident,
+            self.ident,
+          ),
+        };
+        Some(NapiStructExtends {
+          ty: base.clone(),
+          js_name: base_js_name,
+          member,
+        })
+      }
+      None => None,
+    };
+
     for (i, field) in self.fields.iter_mut().enumerate() {
+      if i == 0 && extends.is_some() {
+        continue;
+      }
       match field.vis {
         syn::Visibility::Public(..) => {}
         _ => {
@@ -885,13 +1036,22 @@ impl ConvertToAST for syn::ItemStruct {
     record_struct(&struct_name, js_name.clone(), &opts);
     let namespace = opts.namespace().map(|(m, _)| m.to_owned());
     let implement_iterator = opts.iterator().is_some();
+    let implement_async_iterator = opts.async_iterator().is_some();
+    if let (Some(span), true) = (opts.async_iterator(), implement_iterator) {
+      errors.push(Diagnostic::span_error(
+        *span,
+        "#[napi(iterator)] and #[napi(async_iterator)] can't be used together",
+      ));
+    }
+    let key = namespace
+      .as_ref()
+      .map(|n| format!("{}::{}", n, struct_name))
+      .unwrap_or_else(|| struct_name.to_string());
     GENERATOR_STRUCT.with(|inner| {
-      let mut inner = inner.borrow_mut();
-      let key = namespace
-        .as_ref()
-        .map(|n| format!("{}::{}", n, struct_name))
-        .unwrap_or_else(|| struct_name.to_string());
-      inner.insert(key, implement_iterator);
+      inner.borrow_mut().insert(key.clone(), implement_iterator);
+    });
+    ASYNC_GENERATOR_STRUCT.with(|inner| {
+      inner.borrow_mut().insert(key, implement_async_iterator);
     });
 
     Diagnostic::from_vec(errors).map(|()| Napi {
@@ -905,6 +1065,9 @@ impl ConvertToAST for syn::ItemStruct {
         js_mod: namespace,
         comments: extract_doc_comments(&self.attrs),
         implement_iterator,
+        implement_async_iterator,
+        catch_unwind: opts.abort_on_panic().is_none(),
+        extends,
       }),
     })
   }
@@ -930,17 +1093,19 @@ impl ConvertToAST for syn::ItemImpl {
     let mut iterator_yield_type = None;
     let mut iterator_next_type = None;
     let mut iterator_return_type = None;
+    let mut is_async_iterator = false;
     for item in self.items.iter_mut() {
       if let Some(method) = match item {
         syn::ImplItem::Method(m) => Some(m),
         syn::ImplItem::Type(m) => {
           if let Some((_, t, _)) = &self.trait_ {
             if let Some(PathSegment { ident, .. }) = t.segments.last() {
-              if ident == "Task" && m.ident == "JsValue" {
+              if (ident == "Task" || ident == "ProgressTask") && m.ident == "JsValue" {
                 if let Type::Path(_) = &m.ty {
                   task_output_type = Some(m.ty.clone());
                 }
-              } else if ident == "Generator" {
+              } else if ident == "Generator" || ident == "AsyncGenerator" {
+                is_async_iterator = ident == "AsyncGenerator";
                 if let Type::Path(_) = &m.ty {
                   if m.ident == "Yield" {
                     iterator_yield_type = Some(m.ty.clone());
@@ -1002,6 +1167,7 @@ impl ConvertToAST for syn::ItemImpl {
         iterator_yield_type,
         iterator_next_type,
         iterator_return_type,
+        is_async_iterator,
         js_mod: namespace,
         comments: extract_doc_comments(&self.attrs),
       }),
@@ -1016,13 +1182,62 @@ impl ConvertToAST for syn::ItemEnum {
       _ => bail_span!(self, "only public enum allowed"),
     }
 
-    self.attrs.push(Attribute {
-      pound_token: Default::default(),
-      style: syn::AttrStyle::Outer,
-      bracket_token: Default::default(),
-      path: syn::parse_quote! { derive },
-      tokens: quote! { (Copy, Clone) },
-    });
+    let kind = match (opts.error(), opts.string_enum()) {
+      (Some(_), Some(span)) => {
+        return Err(Diagnostic::span_error(
+          *span,
+          "#[napi(error)] and #[napi(string_enum)] could not be used together",
+        ))
+      }
+      (Some(_), None) => NapiEnumKind::Error,
+      (None, Some(_)) => NapiEnumKind::String,
+      (None, None)
+        if opts.tag().is_some()
+          || opts.content().is_some()
+          || self
+            .variants
+            .iter()
+            .any(|v| !matches!(v.fields, syn::Fields::Unit)) =>
+      {
+        NapiEnumKind::Tagged {
+          tag: opts
+            .tag()
+            .map_or_else(|| "type".to_owned(), |(tag, _)| tag.to_owned()),
+          content: opts.content().map(|(content, _)| content.to_owned()),
+        }
+      }
+      (None, None) => NapiEnumKind::Numeric,
+    };
+
+    if !matches!(kind, NapiEnumKind::Tagged { .. }) {
+      if let Some((_, span)) = opts.tag().or_else(|| opts.content()) {
+        return Err(Diagnostic::span_error(
+          span,
+          "tag and content could not be used with #[napi(error)] or #[napi(string_enum)]",
+        ));
+      }
+    }
+
+    let rename_all = match opts.rename_all() {
+      Some((_, span)) if kind == NapiEnumKind::Numeric || kind == NapiEnumKind::Error => {
+        return Err(Diagnostic::span_error(
+          span,
+          "rename_all is only supported in #[napi(string_enum)] and enums with data-carrying variants",
+        ))
+      }
+      Some((rule, span)) => Some(rename_rule_to_case(rule, span)?),
+      None => None,
+    };
+
+    if kind == NapiEnumKind::Numeric || kind == NapiEnumKind::String {
+      self.attrs.push(Attribute {
+        pound_token: Default::default(),
+        style: syn::AttrStyle::Outer,
+        bracket_token: Default::default(),
+        path: syn::parse_quote! { derive },
+        tokens: quote! { (Copy, Clone) },
+      });
+    }
 
     let js_name = opts
       .js_name()
@@ -1031,10 +1246,59 @@ impl ConvertToAST for syn::ItemEnum {
     let mut last_variant_val: i32 = -1;
     let variants = self
       .variants
-      .iter()
+      .iter_mut()
       .map(|v| {
-        match v.fields {
+        let variant_opts = BindgenAttrs::find(&mut v.attrs)?;
+        let variant_js_name = variant_opts.js_name().map_or_else(
+          || match rename_all {
+            Some(case) => v.ident.to_string().to_case(case),
+            None => v.ident.to_string(),
+          },
+          |(s, _)| s.to_string(),
+        );
+
+        if let (syn::Fields::Unnamed(_), NapiEnumKind::Tagged { content: None, .. }) =
+          (&v.fields, &kind)
+        {
+          bail_span!(
+            v,
+            "tuple variant in the internally tagged enum is not supported, use #[napi(content = \"...\")] to make it adjacently tagged"
+          );
+        }
+
+        let mut fields = vec![];
+        match &mut v.fields {
           syn::Fields::Unit => {}
+          syn::Fields::Named(syn::FieldsNamed { named: f, .. })
+          | syn::Fields::Unnamed(syn::FieldsUnnamed { unnamed: f, .. })
+            if matches!(kind, NapiEnumKind::Error | NapiEnumKind::Tagged { .. }) =>
+          {
+            for (i, field) in f.iter_mut().enumerate() {
+              let field_opts = BindgenAttrs::find(&mut field.attrs)?;
+              let (js_name, name) = match &field.ident {
+                Some(ident) => (
+                  field_opts.js_name().map_or_else(
+                    || ident.unraw().to_string().to_case(Case::Camel),
+                    |(js_name, _)| js_name.to_owned(),
+                  ),
+                  syn::Member::Named(ident.clone()),
+                ),
+                None => (format!("field{}", i), syn::Member::Unnamed(i.into())),
+              };
+              let ignored = field_opts.skip().is_some();
+
+              fields.push(NapiStructField {
+                name,
+                js_name,
+                ty: field.ty.clone(),
+                getter: !ignored,
+                setter: !ignored,
+                comments: extract_doc_comments(&field.attrs),
+                skip_typescript: field_opts.skip_typescript().is_some(),
+                ts_type: field_opts.ts_type().map(|e| e.0.to_string()),
+              })
+            }
+          }
           _ => bail_span!(v.fields, "Structured enum is not supported in #[napi]"),
         };
 
@@ -1080,7 +1344,9 @@ impl ConvertToAST for syn::ItemEnum {
 
         Ok(NapiEnumVariant {
           name: v.ident.clone(),
+          js_name: variant_js_name,
           val,
+          fields,
           comments: extract_doc_comments(&v.attrs),
         })
       })
@@ -1094,11 +1360,35 @@ impl ConvertToAST for syn::ItemEnum {
         js_mod: opts.namespace().map(|(m, _)| m.to_owned()),
         comments: extract_doc_comments(&self.attrs),
         skip_typescript: opts.skip_typescript().is_some(),
+        kind,
       }),
     })
   }
 }
 
+/// `rename_all` rules are the same as `#[serde(rename_all = "...")]`
+fn rename_rule_to_case(rule: &str, span: Span) -> BindgenResult<Case> {
+  Ok(match rule {
+    "lowercase" => Case::Flat,
+    "UPPERCASE" => Case::UpperFlat,
+    "PascalCase" => Case::Pascal,
+    "camelCase" => Case::Camel,
+    "snake_case" => Case::Snake,
+    "SCREAMING_SNAKE_CASE" => Case::ScreamingSnake,
+    "kebab-case" => Case::Kebab,
+    "SCREAMING-KEBAB-CASE" => Case::Cobol,
+    _ => {
+      return Err(Diagnostic::span_error(
+        span,
+        format!(
+          "unknown rename_all rule `{}`, expect one of `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or `SCREAMING-KEBAB-CASE`",
+          rule
+        ),
+      ))
+    }
+  })
+}
+
 impl ConvertToAST for syn::ItemConst {
   fn convert_to_ast(&mut self, opts: BindgenAttrs) -> BindgenResult<Napi> {
     match self.vis {
@@ -2,12 +2,12 @@ use std::ffi::CStr;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
-use std::rc::Rc;
 use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
 
 use crate::{
-  bindgen_runtime::ToNapiValue, check_status, js_values::NapiValue, sys, Env, JsError, JsObject,
-  Result, Task,
+  bindgen_runtime::ToNapiValue, check_status, js_values::NapiValue, panic::catch_unwind, sys, Env,
+  JsError, JsObject, Result, Task,
 };
 
 struct AsyncWork<T: Task> {
@@ -15,7 +15,7 @@ struct AsyncWork<T: Task> {
   deferred: sys::napi_deferred,
   value: Result<mem::MaybeUninit<T::Output>>,
   napi_async_work: sys::napi_async_work,
-  status: Rc<AtomicU8>,
+  status: Arc<AtomicU8>,
 }
 
 pub struct AsyncWorkPromise {
@@ -27,7 +27,7 @@ pub struct AsyncWorkPromise {
   /// 0: not started
   /// 1: completed
   /// 2: canceled
-  pub(crate) status: Rc<AtomicU8>,
+  pub(crate) status: Arc<AtomicU8>,
 }
 
 impl AsyncWorkPromise {
@@ -42,17 +42,13 @@ impl AsyncWorkPromise {
   }
 }
 
-pub fn run<T: Task>(
-  env: sys::napi_env,
-  task: T,
-  abort_status: Option<Rc<AtomicU8>>,
-) -> Result<AsyncWorkPromise> {
+pub fn run<T: Task>(env: sys::napi_env, task: T) -> Result<AsyncWorkPromise> {
   let mut raw_resource = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_object(env, &mut raw_resource) })?;
   let mut raw_promise = ptr::null_mut();
   let mut deferred = ptr::null_mut();
   check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut raw_promise) })?;
-  let task_status = abort_status.unwrap_or_else(|| Rc::new(AtomicU8::new(0)));
+  let task_status = Arc::new(AtomicU8::new(0));
   let result = Box::leak(Box::new(AsyncWork {
     inner_task: task,
     deferred,
@@ -96,7 +92,7 @@ unsafe extern "C" fn execute<T: Task>(_env: sys::napi_env, data: *mut c_void) {
   let mut work = unsafe { Box::from_raw(data as *mut AsyncWork<T>) };
   let _ = mem::replace(
     &mut work.value,
-    work.inner_task.compute().map(mem::MaybeUninit::new),
+    catch_unwind(|| work.inner_task.compute()).map(mem::MaybeUninit::new),
   );
   Box::leak(work);
 }
@@ -113,11 +109,13 @@ unsafe extern "C" fn complete<T: Task>(
   let value = match value_ptr {
     Ok(v) => {
       let output = unsafe { v.assume_init() };
-      work
-        .inner_task
-        .resolve(unsafe { Env::from_raw(env) }, output)
+      catch_unwind(|| {
+        work
+          .inner_task
+          .resolve(unsafe { Env::from_raw(env) }, output)
+      })
     }
-    Err(e) => work.inner_task.reject(unsafe { Env::from_raw(env) }, e),
+    Err(e) => catch_unwind(|| work.inner_task.reject(unsafe { Env::from_raw(env) }, e)),
   };
   if status != sys::Status::napi_cancelled && work.status.load(Ordering::Relaxed) != 2 {
     match check_status!(status)
@@ -135,7 +133,7 @@ unsafe extern "C" fn complete<T: Task>(
       }
     };
   }
-  if let Err(e) = work.inner_task.finally(unsafe { Env::from_raw(env) }) {
+  if let Err(e) = catch_unwind(|| work.inner_task.finally(unsafe { Env::from_raw(env) })) {
     debug_assert!(false, "Panic in Task finally fn: {:?}", e);
   }
   let delete_status = unsafe { sys::napi_delete_async_work(env, napi_async_work) };
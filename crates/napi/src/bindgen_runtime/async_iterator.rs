@@ -0,0 +1,891 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::future::Future;
+use std::os::raw::c_char;
+use std::pin::Pin;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use futures_util::lock::Mutex;
+use futures_util::task::AtomicWaker;
+
+use crate::{
+  bindgen_prelude::{execute_future, AsyncMethodGuard, Unknown},
+  check_status, sys, Env, Error, JsError, Result, Value, ValueType,
+};
+
+use super::{get_well_known_symbol, FromNapiValue, ToNapiValue};
+
+const ASYNC_GENERATOR_INSTANCE_KEY: &str = "[[AsyncGeneratorInstance]]\0";
+
+/// The `Future` returned by the [`AsyncGenerator`] methods, it's polled by the [`crate::executor::Executor`].
+pub type AsyncGeneratorFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Implement an async iterator for the JavaScript Class, so it could be consumed by `for await (const item of instance)`.
+/// This feature is an experimental feature and is not yet stable.
+pub trait AsyncGenerator {
+  type Yield: ToNapiValue + Send + 'static;
+  type Next: FromNapiValue;
+  type Return: FromNapiValue;
+
+  /// Handle the `AsyncGenerator.next()`, the generator is completed if the `Future` resolves to `None` or `Err`
+  /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AsyncGenerator/next
+  fn next(&mut self, value: Option<Self::Next>) -> AsyncGeneratorFuture<'_, Option<Self::Yield>>;
+
+  #[allow(unused_variables)]
+  /// Implement complete to handle the `AsyncGenerator.return()`
+  /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AsyncGenerator/return
+  fn complete(&mut self, value: Option<Self::Return>) -> AsyncGeneratorFuture<'_, ()> {
+    Box::pin(async { Ok(()) })
+  }
+
+  #[allow(unused_variables)]
+  /// Implement catch to handle the `AsyncGenerator.throw()`, the returned `Promise` is rejected with the `Err` value
+  /// https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AsyncGenerator/throw
+  fn catch(
+    &mut self,
+    env: Env,
+    value: Unknown,
+  ) -> std::result::Result<AsyncGeneratorFuture<'_, Option<Self::Yield>>, Unknown> {
+    Err(value)
+  }
+}
+
+/// Wrapped in the async iterator object returned by `[Symbol.asyncIterator]()`
+struct AsyncIteratorState<T> {
+  generator_ptr: *mut T,
+  done: Arc<AtomicBool>,
+  calls: Rc<CallQueue>,
+}
+
+#[derive(Clone, Copy)]
+enum GeneratorMethod {
+  Next,
+  Return,
+  Throw,
+}
+
+/// A `next()`, `return()` or `throw()` call made while another one is pending
+struct QueuedCall {
+  method: GeneratorMethod,
+  /// `[this, arg]`, referenced as an array since the primitive `arg` can't be referenced directly
+  call: sys::napi_ref,
+  has_arg: bool,
+  deferred: sys::napi_deferred,
+}
+
+/// Like the native async generator, the calls on the async iterator are run one at a time in order.
+#[derive(Default)]
+struct CallQueue {
+  pending: Cell<bool>,
+  draining: Cell<bool>,
+  queued: RefCell<VecDeque<QueuedCall>>,
+}
+
+impl CallQueue {
+  /// Queue the call, the returned `Promise` is resolved with the one of the call once it's started
+  unsafe fn enqueue(
+    &self,
+    env: sys::napi_env,
+    method: GeneratorMethod,
+    this: sys::napi_value,
+    arg: Option<sys::napi_value>,
+  ) -> Result<sys::napi_value> {
+    let mut promise = ptr::null_mut();
+    let mut deferred = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) },
+      "Failed to create Promise"
+    )?;
+    // the async iterator and the argument are kept alive until the call is started
+    let mut call = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_array_with_length(env, 2, &mut call) },
+      "Failed to create the queued call"
+    )?;
+    for (index, value) in [Some(this), arg].into_iter().enumerate() {
+      if let Some(value) = value {
+        check_status!(
+          unsafe { sys::napi_set_element(env, call, index as u32, value) },
+          "Failed to set the queued call"
+        )?;
+      }
+    }
+    let mut call_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, call, 1, &mut call_ref) },
+      "Failed to reference the queued call"
+    )?;
+    self.queued.borrow_mut().push_back(QueuedCall {
+      method,
+      call: call_ref,
+      has_arg: arg.is_some(),
+      deferred,
+    });
+    Ok(promise)
+  }
+
+  /// Start the queued calls until one of them is pending
+  unsafe fn drain<T: AsyncGenerator + 'static>(&self, env: sys::napi_env) {
+    // the calls settled synchronously are drained by the outer loop
+    if self.draining.replace(true) {
+      return;
+    }
+    while !self.pending.get() {
+      let call = match self.queued.borrow_mut().pop_front() {
+        Some(call) => call,
+        None => break,
+      };
+      let promise = unsafe { call.start::<T>(env) };
+      let status = match promise {
+        Ok(promise) => unsafe { sys::napi_resolve_deferred(env, call.deferred, promise) },
+        Err(e) => unsafe {
+          sys::napi_reject_deferred(env, call.deferred, JsError::from(e).into_value(env))
+        },
+      };
+      debug_assert!(
+        status == sys::Status::napi_ok,
+        "Settle queued async iterator call failed"
+      );
+    }
+    self.draining.set(false);
+  }
+}
+
+impl QueuedCall {
+  unsafe fn start<T: AsyncGenerator + 'static>(
+    &self,
+    env: sys::napi_env,
+  ) -> Result<sys::napi_value> {
+    let mut call = ptr::null_mut();
+    let status = unsafe { sys::napi_get_reference_value(env, self.call, &mut call) };
+    unsafe { sys::napi_delete_reference(env, self.call) };
+    check_status!(status, "Failed to get the queued call")?;
+    let mut this = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_element(env, call, 0, &mut this) },
+      "Failed to get the queued call"
+    )?;
+    let arg = if self.has_arg {
+      let mut arg = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_element(env, call, 1, &mut arg) },
+        "Failed to get the queued call"
+      )?;
+      Some(arg)
+    } else {
+      None
+    };
+    unsafe { run_generator_method::<T>(env, this, self.method, arg) }
+  }
+}
+
+/// Held by the running call until it's settled, then the queued calls are started.
+struct PendingCall<T: AsyncGenerator + 'static> {
+  env: sys::napi_env,
+  calls: Rc<CallQueue>,
+  guard: Option<AsyncMethodGuard<T>>,
+}
+
+// The pending call is only created and dropped on the JavaScript thread
+unsafe impl<T: AsyncGenerator> Send for PendingCall<T> {}
+unsafe impl<T: AsyncGenerator> Sync for PendingCall<T> {}
+
+impl<T: AsyncGenerator + 'static> Drop for PendingCall<T> {
+  fn drop(&mut self) {
+    // release the borrow of the generator before starting the next call
+    self.guard.take();
+    self.calls.pending.set(false);
+    unsafe { self.calls.drain::<T>(self.env) };
+  }
+}
+
+/// The iterator result of `return()`, it's created in the `return()` call and resolved later.
+struct IteratorResultRef(sys::napi_ref);
+
+// Only accessed on the JavaScript thread
+unsafe impl Send for IteratorResultRef {}
+unsafe impl Sync for IteratorResultRef {}
+
+impl IteratorResultRef {
+  /// Create `{ value, done: true }`
+  fn new(env: sys::napi_env, value: Option<sys::napi_value>) -> Result<Self> {
+    let mut result = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env, &mut result) },
+      "Failed to create iterator result object",
+    )?;
+    if let Some(value) = value {
+      check_status!(
+        unsafe {
+          sys::napi_set_named_property(env, result, "value\0".as_ptr() as *const c_char, value)
+        },
+        "Failed to set iterator result value",
+      )?;
+    }
+    let done = unsafe { bool::to_napi_value(env, true)? };
+    check_status!(
+      unsafe {
+        sys::napi_set_named_property(env, result, "done\0".as_ptr() as *const c_char, done)
+      },
+      "Failed to set iterator result done",
+    )?;
+    let mut result_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, result, 1, &mut result_ref) },
+      "Failed to reference the iterator result"
+    )?;
+    Ok(IteratorResultRef(result_ref))
+  }
+
+  fn into_value(self, env: sys::napi_env) -> Result<sys::napi_value> {
+    let mut result = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, self.0, &mut result) },
+      "Failed to get the iterator result"
+    )?;
+    check_status!(
+      unsafe { sys::napi_delete_reference(env, self.0) },
+      "Failed to delete the iterator result reference"
+    )?;
+    Ok(result)
+  }
+}
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn create_async_iterator<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+  generator_ptr: *mut T,
+) {
+  if let Err(e) = unsafe { define_async_iterator::<T>(env, instance, generator_ptr) } {
+    unsafe { JsError::from(e).throw_into(env) };
+  }
+}
+
+pub(crate) fn get_async_iterator_symbol(env: sys::napi_env) -> Result<sys::napi_value> {
+  get_well_known_symbol(env, "asyncIterator\0")
+}
+
+unsafe fn define_async_iterator<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  instance: sys::napi_value,
+  generator_ptr: *mut T,
+) -> Result<()> {
+  let async_iterator_symbol = get_async_iterator_symbol(env)?;
+  let mut generator_function = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_function(
+        env,
+        "AsyncIterator\0".as_ptr() as *const c_char,
+        13,
+        Some(symbol_async_generator::<T>),
+        generator_ptr as *mut c_void,
+        &mut generator_function,
+      )
+    },
+    "Create async iterator function failed",
+  )?;
+  check_status!(
+    unsafe { sys::napi_set_property(env, instance, async_iterator_symbol, generator_function) },
+    "Failed to set Symbol.asyncIterator on class instance",
+  )
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn symbol_async_generator<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { create_async_generator_object::<T>(env, info) }.unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+unsafe fn create_async_generator_object<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> Result<sys::napi_value> {
+  let mut this = ptr::null_mut();
+  let mut argc = 0;
+  let mut generator_ptr = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        ptr::null_mut(),
+        &mut this,
+        &mut generator_ptr,
+      )
+    },
+    "Get callback info from async generator function failed"
+  )?;
+  let mut generator_object = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_object(env, &mut generator_object) },
+    "Create AsyncGenerator object failed"
+  )?;
+
+  let methods: [(&str, sys::napi_callback); 3] = [
+    ("next\0", Some(async_generator_next::<T>)),
+    ("return\0", Some(async_generator_return::<T>)),
+    ("throw\0", Some(async_generator_throw::<T>)),
+  ];
+  let mut properties = methods
+    .iter()
+    .map(|(name, method)| sys::napi_property_descriptor {
+      utf8name: name.as_ptr() as *const c_char,
+      name: ptr::null_mut(),
+      method: *method,
+      getter: None,
+      setter: None,
+      value: ptr::null_mut(),
+      attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+      data: ptr::null_mut(),
+    })
+    .collect::<Vec<_>>();
+  // the class instance must outlive the async iterator
+  properties.push(sys::napi_property_descriptor {
+    utf8name: ASYNC_GENERATOR_INSTANCE_KEY.as_ptr() as *const c_char,
+    name: ptr::null_mut(),
+    method: None,
+    getter: None,
+    setter: None,
+    value: this,
+    attributes: sys::PropertyAttributes::default,
+    data: ptr::null_mut(),
+  });
+  check_status!(
+    unsafe {
+      sys::napi_define_properties(env, generator_object, properties.len(), properties.as_ptr())
+    },
+    "Define properties on AsyncGenerator object failed"
+  )?;
+
+  let state = Box::new(AsyncIteratorState {
+    generator_ptr: generator_ptr as *mut T,
+    done: Arc::new(AtomicBool::new(false)),
+    calls: Rc::new(CallQueue::default()),
+  });
+  check_status!(
+    unsafe {
+      sys::napi_wrap(
+        env,
+        generator_object,
+        Box::into_raw(state) as *mut c_void,
+        Some(finalize_wrapped::<AsyncIteratorState<T>>),
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Wrap AsyncGenerator state failed"
+  )?;
+
+  Ok(generator_object)
+}
+
+unsafe extern "C" fn finalize_wrapped<W>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data as *mut W) });
+}
+
+/// Get the state wrapped in `this` and the first argument
+unsafe fn get_wrapped_and_arg<W>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> Result<(&'static W, Option<sys::napi_value>)> {
+  let (_, state, arg) = unsafe { get_this_wrapped_and_arg(env, info)? };
+  Ok((state, arg))
+}
+
+/// Get `this`, the state wrapped in it and the first argument
+unsafe fn get_this_wrapped_and_arg<W>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> Result<(sys::napi_value, &'static W, Option<sys::napi_value>)> {
+  let mut this = ptr::null_mut();
+  let mut argv: [sys::napi_value; 1] = [ptr::null_mut()];
+  let mut argc = 1;
+  check_status!(
+    unsafe {
+      sys::napi_get_cb_info(
+        env,
+        info,
+        &mut argc,
+        argv.as_mut_ptr(),
+        &mut this,
+        ptr::null_mut(),
+      )
+    },
+    "Get callback info from async generator function failed"
+  )?;
+  let state = unsafe { unwrap_state(env, this)? };
+  Ok((this, state, if argc == 0 { None } else { Some(argv[0]) }))
+}
+
+unsafe fn unwrap_state<W>(env: sys::napi_env, this: sys::napi_value) -> Result<&'static W> {
+  let mut state = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_unwrap(env, this, &mut state) },
+    "Get async iterator state failed"
+  )?;
+  Ok(unsafe { &*(state as *const W) })
+}
+
+fn iterator_result<V: ToNapiValue>(
+  env: sys::napi_env,
+  value: Option<V>,
+) -> Result<sys::napi_value> {
+  let mut result = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_object(env, &mut result) },
+    "Failed to create iterator result object",
+  )?;
+  let done = value.is_none();
+  if let Some(value) = value {
+    let value = unsafe { V::to_napi_value(env, value)? };
+    check_status!(
+      unsafe {
+        sys::napi_set_named_property(env, result, "value\0".as_ptr() as *const c_char, value)
+      },
+      "Failed to set iterator result value",
+    )?;
+  }
+  let done = unsafe { bool::to_napi_value(env, done)? };
+  check_status!(
+    unsafe { sys::napi_set_named_property(env, result, "done\0".as_ptr() as *const c_char, done) },
+    "Failed to set iterator result done",
+  )?;
+  Ok(result)
+}
+
+/// Resolve `{ value: undefined, done: true }` without touching the generator
+fn resolve_done<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  call: PendingCall<T>,
+) -> Result<sys::napi_value> {
+  execute_future(env, async { Ok(None) }, move |env, value| {
+    let _call = call;
+    iterator_result::<T::Yield>(env, value)
+  })
+}
+
+/// Poll the `Future` borrowing the generator, the generator is completed unless it yields a value
+fn execute_generator_future<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  state: &AsyncIteratorState<T>,
+  call: PendingCall<T>,
+  fut: AsyncGeneratorFuture<'static, Option<T::Yield>>,
+) -> Result<sys::napi_value> {
+  let done = state.done.clone();
+  execute_future(
+    env,
+    async move {
+      let result = fut.await;
+      if !matches!(result, Ok(Some(_))) {
+        done.store(true, Ordering::Relaxed);
+      }
+      result
+    },
+    move |env, value| {
+      // dropped on the JavaScript thread
+      let _call = call;
+      iterator_result(env, value)
+    },
+  )
+}
+
+extern "C" fn async_generator_next<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { call_generator::<T>(env, info, GeneratorMethod::Next) }
+}
+
+extern "C" fn async_generator_return<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { call_generator::<T>(env, info, GeneratorMethod::Return) }
+}
+
+extern "C" fn async_generator_throw<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { call_generator::<T>(env, info, GeneratorMethod::Throw) }
+}
+
+/// Like the native async generator, the call is queued if another one is pending,
+/// and the errors reject the returned `Promise` instead of being thrown.
+unsafe fn call_generator<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+  method: GeneratorMethod,
+) -> sys::napi_value {
+  let call = || {
+    let (this, state, arg) =
+      unsafe { get_this_wrapped_and_arg::<AsyncIteratorState<T>>(env, info)? };
+    if state.calls.pending.get() {
+      return unsafe { state.calls.enqueue(env, method, this, arg) };
+    }
+    unsafe { run_generator_method::<T>(env, this, method, arg) }
+      .or_else(|e| reject_with_error(env, e))
+  };
+  call().unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+unsafe fn run_generator_method<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  this: sys::napi_value,
+  method: GeneratorMethod,
+  arg: Option<sys::napi_value>,
+) -> Result<sys::napi_value> {
+  let state = unsafe { unwrap_state::<AsyncIteratorState<T>>(env, this)? };
+  state.calls.pending.set(true);
+  let call = PendingCall {
+    env,
+    calls: state.calls.clone(),
+    guard: None,
+  };
+  match method {
+    GeneratorMethod::Next => unsafe { generator_next(env, state, call, arg) },
+    GeneratorMethod::Return => unsafe { generator_return(env, state, call, arg) },
+    GeneratorMethod::Throw => unsafe { generator_throw(env, state, call, arg) },
+  }
+}
+
+unsafe fn generator_next<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  state: &AsyncIteratorState<T>,
+  mut call: PendingCall<T>,
+  arg: Option<sys::napi_value>,
+) -> Result<sys::napi_value> {
+  if state.done.load(Ordering::Relaxed) {
+    return resolve_done(env, call);
+  }
+  let value = match arg {
+    Some(arg) => Some(unsafe { T::Next::from_napi_value(env, arg)? }),
+    None => None,
+  };
+  call.guard = Some(unsafe { AsyncMethodGuard::new(env, state.generator_ptr, true, "next")? });
+  let generator = unsafe { Box::leak(Box::from_raw(state.generator_ptr)) };
+  execute_generator_future(env, state, call, generator.next(value))
+}
+
+unsafe fn generator_return<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  state: &AsyncIteratorState<T>,
+  mut call: PendingCall<T>,
+  arg: Option<sys::napi_value>,
+) -> Result<sys::napi_value> {
+  // `{ value, done: true }` is created now, it's resolved after `complete` is finished
+  let result_ref = IteratorResultRef::new(env, arg)?;
+  let resolve_return_value = move |env: sys::napi_env, _: ()| result_ref.into_value(env);
+
+  if state.done.load(Ordering::Relaxed) {
+    return execute_future(env, async { Ok(()) }, move |env, value| {
+      let _call = call;
+      resolve_return_value(env, value)
+    });
+  }
+  let value = match arg {
+    Some(arg) => Some(unsafe { T::Return::from_napi_value(env, arg)? }),
+    None => None,
+  };
+  call.guard = Some(unsafe { AsyncMethodGuard::new(env, state.generator_ptr, true, "return")? });
+  state.done.store(true, Ordering::Relaxed);
+  let generator = unsafe { Box::leak(Box::from_raw(state.generator_ptr)) };
+  let fut = generator.complete(value);
+  execute_future(env, fut, move |env, value| {
+    let _call = call;
+    resolve_return_value(env, value)
+  })
+}
+
+unsafe fn generator_throw<T: AsyncGenerator + 'static>(
+  env: sys::napi_env,
+  state: &AsyncIteratorState<T>,
+  mut call: PendingCall<T>,
+  arg: Option<sys::napi_value>,
+) -> Result<sys::napi_value> {
+  let value = match arg {
+    Some(arg) => Unknown(Value {
+      env,
+      value: arg,
+      value_type: ValueType::Unknown,
+    }),
+    None => {
+      let mut undefined = ptr::null_mut();
+      check_status!(
+        unsafe { sys::napi_get_undefined(env, &mut undefined) },
+        "Get undefined failed"
+      )?;
+      Unknown(Value {
+        env,
+        value: undefined,
+        value_type: ValueType::Undefined,
+      })
+    }
+  };
+  if state.done.load(Ordering::Relaxed) {
+    return reject_with(env, value);
+  }
+  call.guard = Some(unsafe { AsyncMethodGuard::new(env, state.generator_ptr, true, "throw")? });
+  let generator = unsafe { Box::leak(Box::from_raw(state.generator_ptr)) };
+  match generator.catch(Env(env), value) {
+    Ok(fut) => execute_generator_future(env, state, call, fut),
+    Err(value) => {
+      state.done.store(true, Ordering::Relaxed);
+      reject_with(env, value)
+    }
+  }
+}
+
+/// Create a `Promise` rejected with the JavaScript value
+fn reject_with(env: sys::napi_env, value: Unknown) -> Result<sys::napi_value> {
+  let mut promise = ptr::null_mut();
+  let mut deferred = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) },
+    "Failed to create Promise"
+  )?;
+  check_status!(
+    unsafe { sys::napi_reject_deferred(env, deferred, value.0.value) },
+    "Failed to reject Promise"
+  )?;
+  Ok(promise)
+}
+
+/// Create a `Promise` rejected with the `Error`
+fn reject_with_error(env: sys::napi_env, e: Error) -> Result<sys::napi_value> {
+  let mut promise = ptr::null_mut();
+  let mut deferred = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) },
+    "Failed to create Promise"
+  )?;
+  check_status!(
+    unsafe { sys::napi_reject_deferred(env, deferred, JsError::from(e).into_value(env)) },
+    "Failed to reject Promise"
+  )?;
+  Ok(promise)
+}
+
+type BoxedStream<T> = Pin<Box<dyn Stream<Item = Result<T>> + Send>>;
+
+/// Wrapped in the async iterable object created from the `Stream`, `None` if the `Stream` is finished or cancelled
+struct StreamState<T> {
+  stream: Arc<Mutex<Option<BoxedStream<T>>>>,
+  cancel: Arc<CancelStream>,
+}
+
+/// Set by `return()` to stop the pending `next()` from polling the `Stream`, so the lock is released
+#[derive(Default)]
+struct CancelStream {
+  cancelled: AtomicBool,
+  waker: AtomicWaker,
+}
+
+impl CancelStream {
+  fn cancel(&self) {
+    self.cancelled.store(true, Ordering::Release);
+    self.waker.wake();
+  }
+}
+
+/// Resolved with the next item of the `Stream`, or `None` once it's cancelled
+struct NextItem<'a, T> {
+  stream: &'a mut BoxedStream<T>,
+  cancel: &'a CancelStream,
+}
+
+impl<'a, T> Future for NextItem<'a, T> {
+  type Output = Option<Result<T>>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    self.cancel.waker.register(cx.waker());
+    if self.cancel.cancelled.load(Ordering::Acquire) {
+      return Poll::Ready(None);
+    }
+    self.stream.as_mut().poll_next(cx)
+  }
+}
+
+/// Convert the `Stream` returned by the `#[napi]` function into a JavaScript `AsyncIterable`.
+///
+/// The items are pulled by the [`crate::executor::Executor`] one at a time when `next()` is called,
+/// and the `Stream` is dropped once it's finished, errored or cancelled by `return()`.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn create_stream_iterator<S, T>(env: sys::napi_env, stream: S) -> Result<sys::napi_value>
+where
+  S: Stream<Item = Result<T>> + Send + 'static,
+  T: ToNapiValue + Send + 'static,
+{
+  let mut iterable = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_object(env, &mut iterable) },
+    "Create AsyncIterable object failed"
+  )?;
+  let async_iterator_symbol = get_async_iterator_symbol(env)?;
+  let properties = [
+    (
+      "next\0".as_ptr() as *const c_char,
+      ptr::null_mut(),
+      Some(stream_next::<T> as unsafe extern "C" fn(_, _) -> _),
+    ),
+    (
+      "return\0".as_ptr() as *const c_char,
+      ptr::null_mut(),
+      Some(stream_return::<T> as unsafe extern "C" fn(_, _) -> _),
+    ),
+    (
+      ptr::null(),
+      async_iterator_symbol,
+      Some(stream_async_iterator as unsafe extern "C" fn(_, _) -> _),
+    ),
+  ]
+  .iter()
+  .map(|(utf8name, name, method)| sys::napi_property_descriptor {
+    utf8name: *utf8name,
+    name: *name,
+    method: *method,
+    getter: None,
+    setter: None,
+    value: ptr::null_mut(),
+    attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+    data: ptr::null_mut(),
+  })
+  .collect::<Vec<_>>();
+  check_status!(
+    unsafe { sys::napi_define_properties(env, iterable, properties.len(), properties.as_ptr()) },
+    "Define properties on AsyncIterable object failed"
+  )?;
+
+  let stream: BoxedStream<T> = Box::pin(stream);
+  let state = Box::new(StreamState {
+    stream: Arc::new(Mutex::new(Some(stream))),
+    cancel: Arc::new(CancelStream::default()),
+  });
+  check_status!(
+    unsafe {
+      sys::napi_wrap(
+        env,
+        iterable,
+        Box::into_raw(state) as *mut c_void,
+        Some(finalize_wrapped::<StreamState<T>>),
+        ptr::null_mut(),
+        ptr::null_mut(),
+      )
+    },
+    "Wrap Stream state failed"
+  )?;
+  Ok(iterable)
+}
+
+/// `[Symbol.asyncIterator]()` returns the object itself
+unsafe extern "C" fn stream_async_iterator(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut this = ptr::null_mut();
+  let status = unsafe {
+    sys::napi_get_cb_info(
+      env,
+      info,
+      &mut 0,
+      ptr::null_mut(),
+      &mut this,
+      ptr::null_mut(),
+    )
+  };
+  debug_assert!(
+    status == sys::Status::napi_ok,
+    "Get this of AsyncIterable failed"
+  );
+  this
+}
+
+unsafe extern "C" fn stream_next<T: ToNapiValue + Send + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let next = || {
+    let (state, _) = unsafe { get_wrapped_and_arg::<StreamState<T>>(env, info)? };
+    let stream = state.stream.clone();
+    let cancel = state.cancel.clone();
+    execute_future(
+      env,
+      async move {
+        // the pending `next()` calls are queued by the lock
+        let mut stream = stream.lock().await;
+        let item = match stream.as_mut() {
+          Some(stream) => {
+            NextItem {
+              stream,
+              cancel: &cancel,
+            }
+            .await
+          }
+          None => None,
+        };
+        match item {
+          Some(Ok(value)) => Ok(Some(value)),
+          Some(Err(e)) => {
+            stream.take();
+            Err(e)
+          }
+          None => {
+            stream.take();
+            Ok(None)
+          }
+        }
+      },
+      iterator_result::<T>,
+    )
+  };
+  next().unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+unsafe extern "C" fn stream_return<T: Send + 'static>(
+  env: sys::napi_env,
+  info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let cancel = || {
+    let (state, arg) = unsafe { get_wrapped_and_arg::<StreamState<T>>(env, info)? };
+    let result_ref = IteratorResultRef::new(env, arg)?;
+    let stream = state.stream.clone();
+    // the pending `next()` resolves `{ done: true }` instead of holding the lock forever
+    state.cancel.cancel();
+    execute_future(
+      env,
+      async move {
+        stream.lock().await.take();
+        Ok(())
+      },
+      move |env, _| result_ref.into_value(env),
+    )
+  };
+  cancel().unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
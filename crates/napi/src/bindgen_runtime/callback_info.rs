@@ -1,5 +1,6 @@
 use std::cell::Cell;
 use std::ffi::c_void;
+use std::ops::{Deref, DerefMut};
 use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -7,7 +8,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use lazy_static::lazy_static;
 use thread_local::ThreadLocal;
 
-use crate::{bindgen_prelude::*, check_status, sys, Result};
+use crate::{bindgen_prelude::*, check_pending_exception, check_status, sys, Result};
 
 lazy_static! {
   #[doc(hidden)]
@@ -17,6 +18,7 @@ lazy_static! {
 
 pub struct CallbackInfo<const N: usize> {
   env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
   pub this: sys::napi_value,
   pub args: [sys::napi_value; N],
 }
@@ -58,13 +60,54 @@ impl<const N: usize> CallbackInfo<N> {
       }
     }
 
-    Ok(Self { env, this, args })
+    Ok(Self {
+      env,
+      callback_info,
+      this,
+      args,
+    })
   }
 
   pub fn get_arg(&self, index: usize) -> sys::napi_value {
     self.args[index]
   }
 
+  /// Get all the arguments starting from `index`, empty if there are fewer arguments
+  pub fn get_rest_args(&self, index: usize) -> Result<Vec<sys::napi_value>> {
+    let mut argc = 0;
+    unsafe {
+      check_status!(
+        sys::napi_get_cb_info(
+          self.env,
+          self.callback_info,
+          &mut argc,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        ),
+        "Failed to get the arguments count of napi function call."
+      )?;
+    };
+    if argc <= index {
+      return Ok(vec![]);
+    }
+    let mut args = vec![ptr::null_mut(); argc];
+    unsafe {
+      check_status!(
+        sys::napi_get_cb_info(
+          self.env,
+          self.callback_info,
+          &mut argc,
+          args.as_mut_ptr(),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        ),
+        "Failed to get the rest arguments of napi function call."
+      )?;
+    };
+    Ok(args.split_off(index))
+  }
+
   pub fn this(&self) -> sys::napi_value {
     self.this
   }
@@ -112,6 +155,17 @@ impl<const N: usize> CallbackInfo<N> {
     Ok(instance)
   }
 
+  #[cfg(feature = "executor")]
+  pub fn construct_async_generator<T: AsyncGenerator + 'static>(
+    &self,
+    js_name: &str,
+    obj: T,
+  ) -> Result<sys::napi_value> {
+    let (instance, generator_ptr) = self._construct(js_name, obj)?;
+    crate::__private::create_async_iterator(self.env, instance, generator_ptr);
+    Ok(instance)
+  }
+
   pub fn factory<T: 'static>(&self, js_name: &str, obj: T) -> Result<sys::napi_value> {
     self._factory(js_name, obj).map(|(value, _)| value)
   }
@@ -126,6 +180,17 @@ impl<const N: usize> CallbackInfo<N> {
     Ok(instance)
   }
 
+  #[cfg(feature = "executor")]
+  pub fn async_generator_factory<T: AsyncGenerator + 'static>(
+    &self,
+    js_name: &str,
+    obj: T,
+  ) -> Result<sys::napi_value> {
+    let (instance, generator_ptr) = self._factory(js_name, obj)?;
+    crate::__private::create_async_iterator(self.env, instance, generator_ptr);
+    Ok(instance)
+  }
+
   fn _factory<T: 'static>(&self, js_name: &str, obj: T) -> Result<(sys::napi_value, *mut T)> {
     let this = self.this();
     let mut instance = ptr::null_mut();
@@ -135,12 +200,7 @@ impl<const N: usize> CallbackInfo<N> {
       unsafe { sys::napi_new_instance(self.env, this, 0, ptr::null_mut(), &mut instance) };
     inner.store(false, Ordering::Relaxed);
     // Error thrown in `constructor`
-    if status == sys::Status::napi_pending_exception {
-      let mut exception = ptr::null_mut();
-      unsafe { sys::napi_get_and_clear_last_exception(self.env, &mut exception) };
-      unsafe { sys::napi_throw(self.env, exception) };
-      return Ok((ptr::null_mut(), ptr::null_mut()));
-    }
+    check_pending_exception!(self.env, status)?;
     let obj = Box::new(obj);
     let initial_finalize: Box<dyn FnOnce()> = Box::new(|| {});
     let finalize_callbacks_ptr = Rc::into_raw(Rc::new(Cell::new(Box::into_raw(initial_finalize))));
@@ -202,3 +262,101 @@ impl<const N: usize> CallbackInfo<N> {
     }
   }
 }
+
+/// The remaining arguments of the `#[napi]` function, typed as `...args: Array<T>`.
+///
+/// Must be the last argument of the function.
+pub struct Rest<T>(pub Vec<T>);
+
+impl<T: FromNapiValue> Rest<T> {
+  /// # Safety
+  ///
+  /// `values` must be the arguments of the current function call
+  pub unsafe fn from_napi_values(env: sys::napi_env, values: Vec<sys::napi_value>) -> Result<Self> {
+    values
+      .into_iter()
+      .map(|value| unsafe { T::from_napi_value(env, value) })
+      .collect::<Result<Vec<T>>>()
+      .map(Rest)
+  }
+}
+
+impl<T> Rest<T> {
+  pub fn into_inner(self) -> Vec<T> {
+    self.0
+  }
+}
+
+impl<T> Deref for Rest<T> {
+  type Target = Vec<T>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<T> DerefMut for Rest<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
+
+impl<T> IntoIterator for Rest<T> {
+  type Item = T;
+  type IntoIter = std::vec::IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.0.into_iter()
+  }
+}
+
+/// The JavaScript `this` of the function call, converted to `T`.
+///
+/// It's not one of the JavaScript arguments, so it's excluded from the TypeScript parameters.
+pub struct This<T = Object>(pub T);
+
+impl<T> This<T> {
+  pub fn into_inner(self) -> T {
+    self.0
+  }
+}
+
+impl<T: TypeName> TypeName for This<T> {
+  fn type_name() -> &'static str {
+    T::type_name()
+  }
+
+  fn value_type() -> ValueType {
+    T::value_type()
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for This<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    unsafe { T::from_napi_value(env, napi_val) }.map(This)
+  }
+}
+
+impl<T: ValidateNapiValue> ValidateNapiValue for This<T> {
+  fn type_of() -> Vec<ValueType> {
+    T::type_of()
+  }
+
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    unsafe { T::validate(env, napi_val) }
+  }
+}
+
+impl<T> Deref for This<T> {
+  type Target = T;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl<T> DerefMut for This<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.0
+  }
+}
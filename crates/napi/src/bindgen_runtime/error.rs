@@ -1,3 +1,11 @@
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::{
+  bindgen_runtime::{get_class_constructor, ToNapiValue},
+  check_status, sys, type_of, Error, ErrorKind, JsError, Result, Status, ValueType,
+};
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! check_status_or_throw {
@@ -8,3 +16,131 @@ macro_rules! check_status_or_throw {
     }
   };
 }
+
+/// Constructor of the JavaScript classes generated by `#[napi(error)]` enum.
+///
+/// The `prototype` of these classes are linked to the `Error.prototype` after module registered,
+/// so the constructor only needs to set `message`, `name` and the `stack` like the `Error` constructor.
+#[doc(hidden)]
+pub unsafe extern "C" fn error_class_constructor(
+  env: sys::napi_env,
+  cb: sys::napi_callback_info,
+) -> sys::napi_value {
+  unsafe { init_error_instance(env, cb) }.unwrap_or_else(|e| {
+    unsafe { JsError::from(e).throw_into(env) };
+    ptr::null_mut()
+  })
+}
+
+unsafe fn init_error_instance(
+  env: sys::napi_env,
+  cb: sys::napi_callback_info,
+) -> Result<sys::napi_value> {
+  let mut argc = 1;
+  let mut message = ptr::null_mut();
+  let mut this = ptr::null_mut();
+  let mut new_target = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_cb_info(env, cb, &mut argc, &mut message, &mut this, ptr::null_mut()) },
+    "Failed to get Error class constructor arguments"
+  )?;
+  check_status!(unsafe { sys::napi_get_new_target(env, cb, &mut new_target) })?;
+  if new_target.is_null() {
+    return Err(
+      Error::new(
+        Status::InvalidArg,
+        "Error class constructor cannot be invoked without 'new'".to_owned(),
+      )
+      .with_kind(ErrorKind::TypeError),
+    );
+  }
+  let mut name = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_get_named_property(
+      env,
+      new_target,
+      "name\0".as_ptr() as *const c_char,
+      &mut name,
+    )
+  })?;
+  // `name` and `message` are not enumerable, just like the native `Error`
+  let property = |utf8name: &'static str, value: sys::napi_value| sys::napi_property_descriptor {
+    utf8name: utf8name.as_ptr() as *const c_char,
+    name: ptr::null_mut(),
+    method: None,
+    getter: None,
+    setter: None,
+    value,
+    attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+    data: ptr::null_mut(),
+  };
+  let mut properties = vec![property("name\0", name)];
+  if argc > 0 && type_of!(env, message)? != ValueType::Undefined {
+    properties.push(property("message\0", message));
+  }
+  check_status!(unsafe {
+    sys::napi_define_properties(env, this, properties.len(), properties.as_ptr())
+  })?;
+
+  let mut global = ptr::null_mut();
+  let mut error_ctor = ptr::null_mut();
+  let mut capture_stack_trace = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  check_status!(unsafe {
+    sys::napi_get_named_property(
+      env,
+      global,
+      "Error\0".as_ptr() as *const c_char,
+      &mut error_ctor,
+    )
+  })?;
+  check_status!(unsafe {
+    sys::napi_get_named_property(
+      env,
+      error_ctor,
+      "captureStackTrace\0".as_ptr() as *const c_char,
+      &mut capture_stack_trace,
+    )
+  })?;
+  check_status!(unsafe {
+    sys::napi_call_function(
+      env,
+      error_ctor,
+      capture_stack_trace,
+      1,
+      [this].as_ptr(),
+      ptr::null_mut(),
+    )
+  })?;
+  Ok(this)
+}
+
+/// Create an instance of the JavaScript class generated by `#[napi(error)]` enum variant,
+/// `key` is the Rust path of the variant, see [`crate::bindgen_runtime::register_error_variant_class`].
+#[doc(hidden)]
+pub unsafe fn new_error_instance(
+  env: sys::napi_env,
+  key: &'static str,
+  message: String,
+) -> Result<sys::napi_value> {
+  let ctor_ref = get_class_constructor(key).ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      format!("Failed to get constructor of Error class `{}`", key),
+    )
+  })?;
+  let mut ctor = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_reference_value(env, ctor_ref, &mut ctor) },
+    "Failed to get constructor reference of Error class `{}`",
+    key
+  )?;
+  let message = unsafe { String::to_napi_value(env, message) }?;
+  let mut instance = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_new_instance(env, ctor, 1, [message].as_ptr(), &mut instance) },
+    "Failed to construct Error class `{}`",
+    key
+  )?;
+  Ok(instance)
+}
@@ -1,12 +1,13 @@
 use std::ptr;
 use std::{ffi::c_void, os::raw::c_char};
 
-use crate::Value;
-use crate::{bindgen_runtime::Unknown, check_status_or_throw, sys, Env};
+use crate::{bindgen_runtime::Unknown, check_status, check_status_or_throw, sys, Env};
+use crate::{Value, ValueType};
 
-use super::{FromNapiValue, ToNapiValue};
+use super::{FromNapiValue, ToNapiValue, TypeName};
 
 const GENERATOR_STATE_KEY: &str = "[[GeneratorState]]\0";
+const GENERATOR_INSTANCE_KEY: &str = "[[GeneratorInstance]]\0";
 
 /// Implement a Iterator for the JavaScript Class.
 /// This feature is an experimental feature and is not yet stable.
@@ -34,6 +35,83 @@ pub trait Generator {
   }
 }
 
+/// Any Rust `Iterator` returned to JavaScript as an `Iterable`, so it could be consumed by `for (const item of iterable)`.
+///
+/// `#[napi]` functions returning `impl Iterator<Item = T>` are converted through `JsIterable<T>`.
+/// The items are converted lazily, only when `next()` is called from JavaScript.
+pub struct JsIterable<T> {
+  iter: Box<dyn Iterator<Item = T>>,
+}
+
+impl<T> JsIterable<T> {
+  pub fn new<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = T>,
+    I::IntoIter: 'static,
+  {
+    Self {
+      iter: Box::new(iter.into_iter()),
+    }
+  }
+}
+
+impl<T> Generator for JsIterable<T>
+where
+  T: ToNapiValue,
+{
+  type Yield = T;
+  type Next = Unknown;
+  type Return = Unknown;
+
+  fn next(&mut self, _value: Option<Self::Next>) -> Option<Self::Yield> {
+    self.iter.next()
+  }
+}
+
+impl<T> TypeName for JsIterable<T> {
+  fn type_name() -> &'static str {
+    "Iterable"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: ToNapiValue + 'static> ToNapiValue for JsIterable<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> crate::Result<sys::napi_value> {
+    let mut iterable = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_object(env, &mut iterable) },
+      "Create Iterable object failed"
+    )?;
+    let generator_ptr = Box::into_raw(Box::new(val));
+    check_status!(
+      unsafe {
+        sys::napi_wrap(
+          env,
+          iterable,
+          generator_ptr as *mut c_void,
+          Some(finalize_iterable::<T>),
+          ptr::null_mut(),
+          ptr::null_mut(),
+        )
+      },
+      "Wrap Iterable object failed"
+    )?;
+    create_iterator(env, iterable, generator_ptr);
+    Ok(iterable)
+  }
+}
+
+unsafe extern "C" fn finalize_iterable<T>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Box::from_raw(finalize_data as *mut JsIterable<T>) });
+}
+
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn create_iterator<T: Generator>(
   env: sys::napi_env,
@@ -215,20 +293,35 @@ pub unsafe extern "C" fn symbol_generator<T: Generator>(
     "Create generator state failed"
   );
 
-  let properties = vec![sys::napi_property_descriptor {
-    utf8name: GENERATOR_STATE_KEY.as_ptr() as *const c_char,
-    name: ptr::null_mut(),
-    method: None,
-    getter: None,
-    setter: None,
-    value: generator_state,
-    attributes: sys::PropertyAttributes::writable,
-    data: ptr::null_mut(),
-  }];
+  let properties = vec![
+    sys::napi_property_descriptor {
+      utf8name: GENERATOR_STATE_KEY.as_ptr() as *const c_char,
+      name: ptr::null_mut(),
+      method: None,
+      getter: None,
+      setter: None,
+      value: generator_state,
+      attributes: sys::PropertyAttributes::writable,
+      data: ptr::null_mut(),
+    },
+    // the instance owning the generator must outlive the Generator object
+    sys::napi_property_descriptor {
+      utf8name: GENERATOR_INSTANCE_KEY.as_ptr() as *const c_char,
+      name: ptr::null_mut(),
+      method: None,
+      getter: None,
+      setter: None,
+      value: this,
+      attributes: sys::PropertyAttributes::default,
+      data: ptr::null_mut(),
+    },
+  ];
 
   check_status_or_throw!(
     env,
-    unsafe { sys::napi_define_properties(env, generator_object, 1, properties.as_ptr()) },
+    unsafe {
+      sys::napi_define_properties(env, generator_object, properties.len(), properties.as_ptr())
+    },
     "Define properties on Generator object failed"
   );
 
@@ -1,9 +1,15 @@
 use std::ptr;
 
-use crate::{check_status, sys, Error, JsUnknown, NapiRaw, NapiValue, Result, Status, ValueType};
+use crate::{
+  check_status, error::create_js_error, sys, Error, JsUnknown, NapiRaw, NapiValue, Result, Status,
+  ValueType,
+};
 
+mod abort_signal;
 mod array;
 mod arraybuffer;
+#[cfg(feature = "executor")]
+mod async_iterable;
 #[cfg(feature = "napi6")]
 mod bigint;
 mod boolean;
@@ -13,11 +19,14 @@ mod date;
 mod either;
 mod external;
 mod function;
+mod iterable;
 mod map;
 mod nil;
 mod number;
 mod object;
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+#[cfg(feature = "napi4")]
+mod progress_task;
+#[cfg(feature = "executor")]
 mod promise;
 #[cfg(feature = "serde-json")]
 mod serde;
@@ -29,8 +38,11 @@ mod value_ref;
 pub use crate::js_values::JsUnknown as Unknown;
 #[cfg(feature = "napi5")]
 pub use crate::JsDate as Date;
+pub use abort_signal::*;
 pub use array::*;
 pub use arraybuffer::*;
+#[cfg(feature = "executor")]
+pub use async_iterable::*;
 #[cfg(feature = "napi6")]
 pub use bigint::*;
 pub use buffer::*;
@@ -38,9 +50,12 @@ pub use either::*;
 pub use external::*;
 #[cfg(feature = "napi4")]
 pub use function::*;
+pub use iterable::*;
 pub use nil::*;
 pub use object::*;
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+#[cfg(feature = "napi4")]
+pub use progress_task::*;
+#[cfg(feature = "executor")]
 pub use promise::*;
 pub use string::*;
 pub use symbol::*;
@@ -153,6 +168,14 @@ pub trait ValidateNapiValue: FromNapiValue + TypeName {
       ))
     }
   }
+
+  /// # Safety
+  ///
+  /// this function called to check whether napi value is acceptable without creating a rejected `Promise` like `validate`,
+  /// it's used to select the overload of the function
+  unsafe fn is_valid(env: sys::napi_env, napi_val: sys::napi_value) -> bool {
+    matches!(unsafe { Self::validate(env, napi_val) }, Ok(rejected) if rejected.is_null())
+  }
 }
 
 impl<T: TypeName> TypeName for Option<T> {
@@ -184,6 +207,37 @@ where
   }
 }
 
+impl<T> ValidateNapiValue for Option<T>
+where
+  T: ValidateNapiValue,
+{
+  unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+    let mut val_type = 0;
+
+    check_status!(
+      unsafe { sys::napi_typeof(env, napi_val, &mut val_type) },
+      "Failed to detect napi value type",
+    )?;
+
+    match val_type {
+      sys::ValueType::napi_undefined | sys::ValueType::napi_null => Ok(ptr::null_mut()),
+      _ => unsafe { T::validate(env, napi_val) },
+    }
+  }
+
+  unsafe fn is_valid(env: sys::napi_env, napi_val: sys::napi_value) -> bool {
+    let mut val_type = 0;
+    if unsafe { sys::napi_typeof(env, napi_val, &mut val_type) } != sys::Status::napi_ok {
+      return false;
+    }
+
+    match val_type {
+      sys::ValueType::napi_undefined | sys::ValueType::napi_null => true,
+      _ => unsafe { T::is_valid(env, napi_val) },
+    }
+  }
+}
+
 impl<T> ToNapiValue for Option<T>
 where
   T: ToNapiValue,
@@ -210,17 +264,7 @@ where
   unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
     match val {
       Ok(v) => unsafe { T::to_napi_value(env, v) },
-      Err(e) => {
-        let error_code = unsafe { String::to_napi_value(env, format!("{:?}", e.status))? };
-        let reason = unsafe { String::to_napi_value(env, e.reason)? };
-        let mut error = ptr::null_mut();
-        check_status!(
-          unsafe { sys::napi_create_error(env, error_code, reason, &mut error) },
-          "Failed to create napi error"
-        )?;
-
-        Ok(error)
-      }
+      Err(e) => unsafe { create_js_error(env, e.kind, &e) },
     }
   }
 }
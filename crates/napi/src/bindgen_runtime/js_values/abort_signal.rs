@@ -0,0 +1,512 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::c_void;
+use std::future::Future;
+use std::mem;
+use std::os::raw::c_char;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use lazy_static::lazy_static;
+
+use super::{get_named_property, type_of, FromNapiValue, TypeName};
+use crate::{check_status, panic::catch_unwind, sys, Error, JsError, Result, Status, ValueType};
+
+/// <https://developer.mozilla.org/zh-CN/docs/Web/API/AbortSignal>
+///
+/// Could be passed to [`super::AsyncTask::with_signal`], or taken as an argument of an `async fn`,
+/// the running `Future` is dropped once the signal is aborted and the returned `Promise` is rejected with `AbortError`.
+///
+/// All the `AbortSignal`s converted from the same JavaScript object share their state,
+/// so one signal could be passed to any number of calls. It's `Send` and `Sync`, [`AbortSignal::aborted`] and
+/// [`AbortSignal::cancellation_flag`] could be polled on the background threads.
+#[derive(Clone)]
+pub struct AbortSignal {
+  state: Arc<AbortState>,
+}
+
+impl AbortSignal {
+  /// Whether the signal is aborted, could be called on any thread
+  pub fn aborted(&self) -> bool {
+    self.state.aborted.load(Ordering::SeqCst)
+  }
+
+  /// The `reason` of the aborted signal, returning it to JavaScript throws the original value.
+  ///
+  /// `None` if the signal isn't aborted yet.
+  pub fn reason(&self) -> Option<Error> {
+    lock(&self.state.reason).clone()
+  }
+
+  /// Call `callback` on the JavaScript thread once the `abort` event of the signal is dispatched.
+  ///
+  /// If the signal is already aborted, the `callback` is called right away on the calling thread,
+  /// which could be a background thread.
+  pub fn on_abort<F: 'static + Send + FnOnce()>(&self, callback: F) {
+    if let Some(AbortListener::Callback(callback)) = self
+      .state
+      .add_listener(AbortListener::Callback(Box::new(callback)))
+    {
+      callback();
+    }
+  }
+
+  /// A flag set once the signal is aborted, for the background threads checking the cancellation between their steps
+  ///
+  /// ```rust,ignore
+  /// let cancelled = signal.cancellation_flag();
+  /// std::thread::spawn(move || {
+  ///   for chunk in chunks {
+  ///     if cancelled.load(Ordering::Relaxed) {
+  ///       break;
+  ///     }
+  ///     process(chunk);
+  ///   }
+  /// });
+  /// ```
+  pub fn cancellation_flag(&self) -> Arc<AtomicBool> {
+    self.state.aborted.clone()
+  }
+
+  /// Resolves once the signal is aborted, could be awaited or raced against other futures in an `async fn`.
+  ///
+  /// ```rust,ignore
+  /// #[napi]
+  /// async fn wait_for_abort(signal: AbortSignal) {
+  ///   signal.cancelled().await;
+  /// }
+  /// ```
+  pub fn cancelled(&self) -> Cancelled {
+    Cancelled {
+      waiter: AbortWaiter::new(self.state.clone()),
+    }
+  }
+
+  /// Cancel the async work and reject its `Promise` with `AbortError` once the signal is aborted
+  pub(crate) fn cancel_async_work(
+    &self,
+    env: sys::napi_env,
+    napi_async_work: sys::napi_async_work,
+    deferred: sys::napi_deferred,
+    status: Arc<AtomicU8>,
+  ) {
+    let listener = AbortListener::AsyncWork(AsyncWorkListener {
+      napi_async_work,
+      deferred,
+      status,
+    });
+    if let Some(listener) = self.state.add_listener(listener) {
+      listener.call(env);
+    }
+  }
+}
+
+impl From<AbortSignal> for Arc<AtomicBool> {
+  fn from(signal: AbortSignal) -> Self {
+    signal.cancellation_flag()
+  }
+}
+
+struct AbortState {
+  aborted: Arc<AtomicBool>,
+  reason: Mutex<Option<Error>>,
+  wakers: Mutex<Wakers>,
+  listeners: Mutex<Vec<AbortListener>>,
+}
+
+/// The wakers of the pending [`Cancelled`] and [`Abortable`] futures, one slot for each of them
+#[derive(Default)]
+struct Wakers {
+  next_key: usize,
+  slots: HashMap<usize, Waker>,
+}
+
+impl AbortState {
+  /// The `listener` is given back if the signal is already aborted
+  fn add_listener(&self, listener: AbortListener) -> Option<AbortListener> {
+    let mut listeners = lock(&self.listeners);
+    if self.aborted.load(Ordering::SeqCst) {
+      return Some(listener);
+    }
+    // don't hold the completed tasks of a long living signal
+    listeners.retain(|listener| !listener.is_completed());
+    listeners.push(listener);
+    None
+  }
+
+  fn abort(&self, env: sys::napi_env, reason: Error) {
+    lock(&self.reason).replace(reason);
+    let listeners = {
+      let mut listeners = lock(&self.listeners);
+      self.aborted.store(true, Ordering::SeqCst);
+      mem::take(&mut *listeners)
+    };
+    let wakers = mem::take(&mut lock(&self.wakers).slots);
+    wakers.into_values().for_each(Waker::wake);
+    let mut first_error = None;
+    for listener in listeners {
+      if let Err(err) = catch_unwind(|| {
+        listener.call(env);
+        Ok(())
+      }) {
+        first_error.get_or_insert(err);
+      }
+    }
+    if let Some(err) = first_error {
+      unsafe { JsError::from(err).throw_into(env) };
+    }
+  }
+}
+
+/// A future waiting for the signal, its waker slot is freed once it's dropped
+struct AbortWaiter {
+  state: Arc<AbortState>,
+  key: Option<usize>,
+}
+
+impl AbortWaiter {
+  fn new(state: Arc<AbortState>) -> Self {
+    AbortWaiter { state, key: None }
+  }
+
+  fn poll_aborted(&mut self, cx: &mut Context<'_>) -> bool {
+    if self.state.aborted.load(Ordering::SeqCst) {
+      return true;
+    }
+    {
+      let mut wakers = lock(&self.state.wakers);
+      let key = match self.key {
+        Some(key) => key,
+        None => {
+          let key = wakers.next_key;
+          wakers.next_key = key.wrapping_add(1);
+          self.key = Some(key);
+          key
+        }
+      };
+      let waker = cx.waker();
+      if !matches!(wakers.slots.get(&key), Some(registered) if registered.will_wake(waker)) {
+        wakers.slots.insert(key, waker.clone());
+      }
+    }
+    // the signal may be aborted before the waker is registered
+    self.state.aborted.load(Ordering::SeqCst)
+  }
+}
+
+impl Drop for AbortWaiter {
+  fn drop(&mut self) {
+    if let Some(key) = self.key {
+      lock(&self.state.wakers).slots.remove(&key);
+    }
+  }
+}
+
+enum AbortListener {
+  Callback(Box<dyn FnOnce() + Send>),
+  AsyncWork(AsyncWorkListener),
+}
+
+struct AsyncWorkListener {
+  napi_async_work: sys::napi_async_work,
+  deferred: sys::napi_deferred,
+  /// see `AsyncWorkPromise::status`
+  status: Arc<AtomicU8>,
+}
+
+// The async work is only touched on the JavaScript thread which created it
+unsafe impl Send for AsyncWorkListener {}
+
+impl AbortListener {
+  fn is_completed(&self) -> bool {
+    match self {
+      AbortListener::Callback(_) => false,
+      AbortListener::AsyncWork(work) => work.status.load(Ordering::Relaxed) != 0,
+    }
+  }
+
+  fn call(self, env: sys::napi_env) {
+    match self {
+      AbortListener::Callback(callback) => callback(),
+      AbortListener::AsyncWork(work) => {
+        // Task Completed, return now
+        if work.status.load(Ordering::Relaxed) != 0 {
+          return;
+        }
+        unsafe { sys::napi_cancel_async_work(env, work.napi_async_work) };
+        // abort function must be called from JavaScript main thread, so Relaxed Ordering is ok.
+        work.status.store(2, Ordering::Relaxed);
+        let reject_status = unsafe {
+          sys::napi_reject_deferred(
+            env,
+            work.deferred,
+            JsError::from(abort_error()).into_value(env),
+          )
+        };
+        debug_assert_eq!(
+          reject_status,
+          sys::Status::napi_ok,
+          "{}",
+          "Reject AbortError failed"
+        );
+      }
+    }
+  }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+  mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The `Future` returned by [`AbortSignal::cancelled`]
+pub struct Cancelled {
+  waiter: AbortWaiter,
+}
+
+impl Future for Cancelled {
+  type Output = ();
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    if self.waiter.poll_aborted(cx) {
+      Poll::Ready(())
+    } else {
+      Poll::Pending
+    }
+  }
+}
+
+/// Drop the `Future` of an `async fn` once the `AbortSignal` argument is aborted
+#[doc(hidden)]
+pub struct Abortable<F> {
+  waiter: Option<AbortWaiter>,
+  fut: Pin<Box<F>>,
+}
+
+#[doc(hidden)]
+pub fn abortable<T, F: Future<Output = Result<T>>>(
+  signal: Option<AbortSignal>,
+  fut: F,
+) -> Abortable<F> {
+  Abortable {
+    waiter: signal.map(|signal| AbortWaiter::new(signal.state)),
+    fut: Box::pin(fut),
+  }
+}
+
+impl<T, F: Future<Output = Result<T>>> Future for Abortable<F> {
+  type Output = Result<T>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let this = self.get_mut();
+    if let Some(waiter) = &mut this.waiter {
+      if waiter.poll_aborted(cx) {
+        return Poll::Ready(Err(abort_error()));
+      }
+    }
+    this.fut.as_mut().poll(cx)
+  }
+}
+
+fn abort_error() -> Error {
+  Error::new(Status::Cancelled, "AbortError".to_owned())
+}
+
+impl TypeName for AbortSignal {
+  fn type_name() -> &'static str {
+    "AbortSignal"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl FromNapiValue for AbortSignal {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    // the state is attached to the signal by the first conversion, and shared by the following ones
+    if let Some(state) = unsafe { get_state(env, napi_val) } {
+      return Ok(AbortSignal { state });
+    }
+
+    let add_event_listener = unsafe { get_named_property(env, napi_val, "addEventListener\0") }?;
+    if unsafe { type_of(env, add_event_listener) }? != sys::ValueType::napi_function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not an AbortSignal".to_owned(),
+      ));
+    }
+    let state = Arc::new(AbortState {
+      aborted: Arc::new(AtomicBool::new(false)),
+      reason: Mutex::new(None),
+      wakers: Mutex::new(Wakers::default()),
+      listeners: Mutex::new(Vec::new()),
+    });
+    let aborted = unsafe { get_named_property(env, napi_val, "aborted\0") }?;
+    let mut is_aborted = false;
+    check_status!(unsafe { sys::napi_get_value_bool(env, aborted, &mut is_aborted) })?;
+    if is_aborted {
+      // the `abort` event is already dispatched, no need to listen
+      state.abort(env, unsafe { get_reason(env, napi_val) });
+      return Ok(AbortSignal { state });
+    }
+
+    unsafe { set_state(env, napi_val, &state) }?;
+    let mut listener = ptr::null_mut();
+    let listener_name = "onabort";
+    check_status!(unsafe {
+      sys::napi_create_function(
+        env,
+        listener_name.as_ptr() as *const c_char,
+        listener_name.len(),
+        Some(on_abort),
+        ptr::null_mut(),
+        &mut listener,
+      )
+    })?;
+    let mut event_name = ptr::null_mut();
+    let abort = "abort";
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        abort.as_ptr() as *const c_char,
+        abort.len(),
+        &mut event_name,
+      )
+    })?;
+    let args = [event_name, listener];
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          napi_val,
+          add_event_listener,
+          args.len(),
+          args.as_ptr(),
+          ptr::null_mut(),
+        )
+      },
+      "Listen to the abort event failed"
+    )?;
+    Ok(AbortSignal { state })
+  }
+}
+
+lazy_static! {
+  /// Addresses of the `AbortState`s owned by the `External`s attached to the signals.
+  ///
+  /// The property could be set by anyone, its `External` is only trusted if it's created here.
+  static ref ATTACHED_STATES: Mutex<HashSet<usize>> = Default::default();
+  /// The non-enumerable property holding the `External`,
+  /// distinct for every copy of napi-rs loaded in the process so they don't fight over it.
+  static ref STATE_PROPERTY: String = format!(
+    "__napi_rs_abort_state_{:x}\0",
+    abort_state_finalize as *const () as usize
+  );
+}
+
+/// The state attached to the signal by [`set_state`], `None` if it isn't attached yet
+unsafe fn get_state(env: sys::napi_env, signal: sys::napi_value) -> Option<Arc<AbortState>> {
+  let external = unsafe { get_named_property(env, signal, STATE_PROPERTY.as_str()) }.ok()?;
+  if unsafe { type_of(env, external) }.ok()? != sys::ValueType::napi_external {
+    return None;
+  }
+  let mut data = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_value_external(env, external, &mut data) }).ok()?;
+  let attached_states = lock(&ATTACHED_STATES);
+  if !attached_states.contains(&(data as usize)) {
+    return None;
+  }
+  let state = data as *const AbortState;
+  // the `External` holding the other reference can't be finalized while the states are locked
+  unsafe { Arc::increment_strong_count(state) };
+  Some(unsafe { Arc::from_raw(state) })
+}
+
+unsafe fn set_state(
+  env: sys::napi_env,
+  signal: sys::napi_value,
+  state: &Arc<AbortState>,
+) -> Result<()> {
+  let data = Arc::into_raw(state.clone()) as *mut c_void;
+  lock(&ATTACHED_STATES).insert(data as usize);
+  let mut external = ptr::null_mut();
+  let status = unsafe {
+    sys::napi_create_external(
+      env,
+      data,
+      Some(abort_state_finalize),
+      ptr::null_mut(),
+      &mut external,
+    )
+  };
+  if status != sys::Status::napi_ok {
+    unsafe { abort_state_finalize(env, data, ptr::null_mut()) };
+  }
+  check_status!(status, "Create the state of AbortSignal failed")?;
+  let property = sys::napi_property_descriptor {
+    utf8name: STATE_PROPERTY.as_ptr() as *const c_char,
+    name: ptr::null_mut(),
+    method: None,
+    getter: None,
+    setter: None,
+    value: external,
+    attributes: sys::PropertyAttributes::default,
+    data: ptr::null_mut(),
+  };
+  check_status!(
+    unsafe { sys::napi_define_properties(env, signal, 1, &property) },
+    "Attach the state to AbortSignal failed"
+  )
+}
+
+/// `signal.reason`, which doesn't exist before Node.js 17.2
+unsafe fn get_reason(env: sys::napi_env, signal: sys::napi_value) -> Error {
+  match unsafe { get_named_property(env, signal, "reason\0") } {
+    Ok(reason) if unsafe { type_of(env, reason) }.ok() != Some(sys::ValueType::napi_undefined) => {
+      let mut reason = unsafe { Error::from_js_exception(env, reason) };
+      reason.status = Status::Cancelled;
+      reason
+    }
+    _ => abort_error(),
+  }
+}
+
+extern "C" fn on_abort(
+  env: sys::napi_env,
+  callback_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut this = ptr::null_mut();
+  unsafe {
+    let get_cb_info_status = sys::napi_get_cb_info(
+      env,
+      callback_info,
+      &mut 0,
+      ptr::null_mut(),
+      &mut this,
+      ptr::null_mut(),
+    );
+    debug_assert_eq!(
+      get_cb_info_status,
+      sys::Status::napi_ok,
+      "{}",
+      "Get callback info in AbortSignal abort listener failed"
+    );
+    let state = match get_state(env, this) {
+      Some(state) => state,
+      None => return ptr::null_mut(),
+    };
+    state.abort(env, get_reason(env, this));
+  }
+  ptr::null_mut()
+}
+
+unsafe extern "C" fn abort_state_finalize(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  lock(&ATTACHED_STATES).remove(&(finalize_data as usize));
+  drop(unsafe { Arc::from_raw(finalize_data as *const AbortState) });
+}
@@ -0,0 +1,294 @@
+use std::ffi::c_void;
+use std::future::Future;
+use std::os::raw::c_char;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll};
+
+use futures_channel::oneshot::{channel, Receiver, Sender};
+use futures_core::Stream;
+
+use crate::{
+  bindgen_runtime::async_iterator::get_async_iterator_symbol, check_status, sys, Error, Result,
+  Status, ValueType,
+};
+
+use super::{
+  call_method, get_named_property, type_of, FromNapiValue, IteratorResult, Promise, TypeName,
+  ValidateNapiValue,
+};
+
+/// Any JavaScript object implementing `[Symbol.asyncIterator]()`, for example an async generator or a Node.js `Readable` stream,
+/// consumed as a `Stream` of `Result<T>` in an `async fn`.
+///
+/// The `next()` method of the iterator is called on the JavaScript thread every time the `Stream` is polled for a new item,
+/// and the returned `Promise` is awaited through [`Promise`].
+/// Dropping the `Stream` before it's exhausted calls `return()` on the iterator, so the underlying resources could be released.
+pub struct JsAsyncIterable<T: FromNapiValue + 'static> {
+  tsfn: sys::napi_threadsafe_function,
+  pending: Option<PendingNext<T>>,
+  done: bool,
+}
+
+unsafe impl<T: FromNapiValue + Send + 'static> Send for JsAsyncIterable<T> {}
+
+enum PendingNext<T: FromNapiValue> {
+  /// Waiting for `next()` to be called on the JavaScript thread
+  Calling(Receiver<Result<NextResult<T>>>),
+  /// Waiting for the `Promise` returned by `next()`
+  Awaiting(Promise<IteratorResult<T>>),
+}
+
+enum NextResult<T: FromNapiValue> {
+  Promise(Promise<IteratorResult<T>>),
+  /// `next()` returned the `{ value, done }` object directly
+  Ready(IteratorResult<T>),
+}
+
+enum IteratorRequest<T: FromNapiValue> {
+  Next(Sender<Result<NextResult<T>>>),
+  Return,
+}
+
+impl<T: FromNapiValue + 'static> TypeName for JsAsyncIterable<T> {
+  fn type_name() -> &'static str {
+    "AsyncIterable"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: FromNapiValue + 'static> ValidateNapiValue for JsAsyncIterable<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl<T: FromNapiValue + 'static> FromNapiValue for JsAsyncIterable<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let async_iterator_symbol = get_async_iterator_symbol(env)?;
+    let mut async_iterator_fn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_get_property(env, napi_val, async_iterator_symbol, &mut async_iterator_fn)
+      },
+      "Get [Symbol.asyncIterator] failed"
+    )?;
+    if unsafe { type_of(env, async_iterator_fn) }? != sys::ValueType::napi_function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not async iterable".to_owned(),
+      ));
+    }
+    let mut iterator = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          napi_val,
+          async_iterator_fn,
+          0,
+          ptr::null_mut(),
+          &mut iterator,
+        )
+      },
+      "Call [Symbol.asyncIterator]() failed"
+    )?;
+    let next = unsafe { get_named_property(env, iterator, "next\0") }?;
+    if unsafe { type_of(env, next) }? != sys::ValueType::napi_function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The async iterator doesn't have a `next` method".to_owned(),
+      ));
+    }
+
+    let mut iterator_ref = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, iterator, 1, &mut iterator_ref) },
+      "Create reference of the async iterator failed"
+    )?;
+    let mut async_resource_name = ptr::null_mut();
+    let name = "napi_rs_async_iterable";
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        name.as_ptr() as *const c_char,
+        name.len(),
+        &mut async_resource_name,
+      )
+    })?;
+    let mut tsfn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env,
+          next,
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          ptr::null_mut(),
+          Some(iterator_finalize_cb),
+          iterator_ref as *mut c_void,
+          Some(call_iterator_cb::<T>),
+          &mut tsfn,
+        )
+      },
+      "Create threadsafe function for the async iterator failed"
+    )?;
+    // the pending `async fn` keeps the event loop alive, not the iterator
+    check_status!(unsafe { sys::napi_unref_threadsafe_function(env, tsfn) })?;
+    Ok(Self {
+      tsfn,
+      pending: None,
+      done: false,
+    })
+  }
+}
+
+impl<T: FromNapiValue + 'static> JsAsyncIterable<T> {
+  fn request(&self, request: IteratorRequest<T>) -> Result<()> {
+    let data = Box::into_raw(Box::new(request));
+    let status = unsafe {
+      sys::napi_call_threadsafe_function(
+        self.tsfn,
+        data as *mut c_void,
+        sys::ThreadsafeFunctionCallMode::nonblocking,
+      )
+    };
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Box::from_raw(data) });
+    }
+    check_status!(status, "Call the async iterator failed")
+  }
+}
+
+impl<T: FromNapiValue + 'static> Stream for JsAsyncIterable<T> {
+  type Item = Result<T>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    let this = self.get_mut();
+    loop {
+      if this.done {
+        return Poll::Ready(None);
+      }
+      let result = match this.pending.as_mut() {
+        None => {
+          let (sender, receiver) = channel();
+          if let Err(err) = this.request(IteratorRequest::Next(sender)) {
+            this.done = true;
+            return Poll::Ready(Some(Err(err)));
+          }
+          this.pending = Some(PendingNext::Calling(receiver));
+          continue;
+        }
+        Some(PendingNext::Calling(receiver)) => match Pin::new(receiver).poll(cx) {
+          Poll::Pending => return Poll::Pending,
+          Poll::Ready(Ok(Ok(NextResult::Promise(promise)))) => {
+            this.pending = Some(PendingNext::Awaiting(promise));
+            continue;
+          }
+          Poll::Ready(Ok(Ok(NextResult::Ready(result)))) => Ok(result),
+          Poll::Ready(Ok(Err(err))) => Err(err),
+          Poll::Ready(Err(_)) => Err(Error::new(
+            Status::Closing,
+            "The async iterator is closed".to_owned(),
+          )),
+        },
+        Some(PendingNext::Awaiting(promise)) => match Pin::new(promise).poll(cx) {
+          Poll::Pending => return Poll::Pending,
+          Poll::Ready(result) => result,
+        },
+      };
+      this.pending = None;
+      return match result {
+        Ok(IteratorResult(Some(value))) => Poll::Ready(Some(Ok(value))),
+        Ok(IteratorResult(None)) => {
+          this.done = true;
+          Poll::Ready(None)
+        }
+        Err(err) => {
+          this.done = true;
+          Poll::Ready(Some(Err(err)))
+        }
+      };
+    }
+  }
+}
+
+impl<T: FromNapiValue + 'static> Drop for JsAsyncIterable<T> {
+  fn drop(&mut self) {
+    if !self.done {
+      let _ = self.request(IteratorRequest::Return);
+    }
+    unsafe {
+      sys::napi_release_threadsafe_function(self.tsfn, sys::ThreadsafeFunctionReleaseMode::release)
+    };
+  }
+}
+
+unsafe extern "C" fn call_iterator_cb<T: FromNapiValue + 'static>(
+  env: sys::napi_env,
+  next: sys::napi_value,
+  context: *mut c_void,
+  data: *mut c_void,
+) {
+  let request = unsafe { Box::from_raw(data as *mut IteratorRequest<T>) };
+  // env is null if the environment is shutting down
+  if env.is_null() {
+    return;
+  }
+  let mut iterator = ptr::null_mut();
+  let status =
+    unsafe { sys::napi_get_reference_value(env, context as sys::napi_ref, &mut iterator) };
+  match *request {
+    IteratorRequest::Next(sender) => {
+      let result = check_status!(status, "Get the async iterator failed")
+        .and_then(|_| unsafe { call_next(env, iterator, next) });
+      let _ = sender.send(result);
+    }
+    IteratorRequest::Return => {
+      if status == sys::Status::napi_ok {
+        unsafe { call_return(env, iterator) };
+      }
+    }
+  }
+}
+
+unsafe fn call_next<T: FromNapiValue>(
+  env: sys::napi_env,
+  iterator: sys::napi_value,
+  next: sys::napi_value,
+) -> Result<NextResult<T>> {
+  let result = unsafe { call_method(env, iterator, next) }?;
+  let mut is_promise = false;
+  check_status!(unsafe { sys::napi_is_promise(env, result, &mut is_promise) })?;
+  if is_promise {
+    Ok(NextResult::Promise(unsafe {
+      Promise::from_napi_value(env, result)
+    }?))
+  } else {
+    Ok(NextResult::Ready(unsafe {
+      IteratorResult::from_napi_value(env, result)
+    }?))
+  }
+}
+
+unsafe fn call_return(env: sys::napi_env, iterator: sys::napi_value) {
+  if let Ok(return_fn) = unsafe { get_named_property(env, iterator, "return\0") } {
+    if let Ok(sys::ValueType::napi_function) = unsafe { type_of(env, return_fn) } {
+      // nobody is waiting for the result of `return()`
+      let _ = unsafe { call_method(env, iterator, return_fn) };
+    }
+  }
+}
+
+unsafe extern "C" fn iterator_finalize_cb(
+  env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  unsafe { sys::napi_delete_reference(env, finalize_data as sys::napi_ref) };
+}
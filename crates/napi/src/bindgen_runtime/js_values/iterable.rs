@@ -0,0 +1,193 @@
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::{check_status, sys, Error, Result, Status, ValueType};
+
+use super::{FromNapiValue, TypeName, ValidateNapiValue};
+
+/// Any JavaScript iterable, for example a `Set`, `Map.prototype.entries()` or a generator,
+/// consumed lazily through `[Symbol.iterator]()` as an `Iterator` of `Result<T>`.
+///
+/// Unlike `Vec<T>`, the values are only converted when they are pulled out of the `Iterator`.
+/// The iteration stops at the first error, and `return()` is called on the JavaScript iterator if it's dropped before it's exhausted.
+pub struct JsIterator<T: FromNapiValue> {
+  env: sys::napi_env,
+  iterator: sys::napi_value,
+  next: sys::napi_value,
+  done: bool,
+  _item: PhantomData<T>,
+}
+
+impl<T: FromNapiValue> TypeName for JsIterator<T> {
+  fn type_name() -> &'static str {
+    "Iterable"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Object
+  }
+}
+
+impl<T: FromNapiValue> ValidateNapiValue for JsIterator<T> {
+  fn type_of() -> Vec<ValueType> {
+    vec![ValueType::Object]
+  }
+}
+
+impl<T: FromNapiValue> FromNapiValue for JsIterator<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let iterator_symbol = get_well_known_symbol(env, "iterator\0")?;
+    let mut iterator_fn = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_property(env, napi_val, iterator_symbol, &mut iterator_fn) },
+      "Get [Symbol.iterator] failed"
+    )?;
+    if unsafe { type_of(env, iterator_fn) }? != sys::ValueType::napi_function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "Value is not iterable".to_owned(),
+      ));
+    }
+    let mut iterator = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          napi_val,
+          iterator_fn,
+          0,
+          ptr::null_mut(),
+          &mut iterator,
+        )
+      },
+      "Call [Symbol.iterator]() failed"
+    )?;
+    let next = unsafe { get_named_property(env, iterator, "next\0") }?;
+    if unsafe { type_of(env, next) }? != sys::ValueType::napi_function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The iterator doesn't have a `next` method".to_owned(),
+      ));
+    }
+    Ok(Self {
+      env,
+      iterator,
+      next,
+      done: false,
+      _item: PhantomData,
+    })
+  }
+}
+
+impl<T: FromNapiValue> Iterator for JsIterator<T> {
+  type Item = Result<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+    let result = unsafe { call_method(self.env, self.iterator, self.next) }
+      .and_then(|result| unsafe { IteratorResult::<T>::from_napi_value(self.env, result) });
+    match result {
+      Ok(IteratorResult(Some(value))) => Some(Ok(value)),
+      Ok(IteratorResult(None)) => {
+        self.done = true;
+        None
+      }
+      Err(err) => {
+        self.done = true;
+        Some(Err(err))
+      }
+    }
+  }
+}
+
+impl<T: FromNapiValue> Drop for JsIterator<T> {
+  fn drop(&mut self) {
+    if self.done {
+      return;
+    }
+    let return_fn = unsafe { get_named_property(self.env, self.iterator, "return\0") };
+    if let Ok(return_fn) = return_fn {
+      if let Ok(sys::ValueType::napi_function) = unsafe { type_of(self.env, return_fn) } {
+        // nobody could handle the error thrown by `return()`
+        let _ = unsafe { call_method(self.env, self.iterator, return_fn) };
+      }
+    }
+  }
+}
+
+/// `{ value: T, done: false }` or `{ done: true }`
+pub(crate) struct IteratorResult<T>(pub(crate) Option<T>);
+
+impl<T: FromNapiValue> FromNapiValue for IteratorResult<T> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    let done = unsafe { get_named_property(env, napi_val, "done\0") }?;
+    let mut coerced = ptr::null_mut();
+    let mut is_done = false;
+    check_status!(unsafe { sys::napi_coerce_to_bool(env, done, &mut coerced) })?;
+    check_status!(unsafe { sys::napi_get_value_bool(env, coerced, &mut is_done) })?;
+    if is_done {
+      return Ok(IteratorResult(None));
+    }
+    let value = unsafe { get_named_property(env, napi_val, "value\0") }?;
+    Ok(IteratorResult(Some(unsafe {
+      T::from_napi_value(env, value)
+    }?)))
+  }
+}
+
+/// `Symbol[name]`, `name` must be nul terminated
+pub(crate) fn get_well_known_symbol(env: sys::napi_env, name: &str) -> Result<sys::napi_value> {
+  let mut global = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_get_global(env, &mut global) },
+    "Get global object failed",
+  )?;
+  let symbol_object = unsafe { get_named_property(env, global, "Symbol\0") }?;
+  unsafe { get_named_property(env, symbol_object, name) }
+}
+
+pub(crate) unsafe fn get_named_property(
+  env: sys::napi_env,
+  object: sys::napi_value,
+  name: &str,
+) -> Result<sys::napi_value> {
+  let mut value = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_get_named_property(env, object, name.as_ptr() as *const c_char, &mut value)
+    },
+    "Get property `{}` failed",
+    name.trim_end_matches('\0'),
+  )?;
+  Ok(value)
+}
+
+/// Call `method` on `this` without arguments, the thrown JavaScript exception is turned into the `Err`
+pub(crate) unsafe fn call_method(
+  env: sys::napi_env,
+  this: sys::napi_value,
+  method: sys::napi_value,
+) -> Result<sys::napi_value> {
+  let mut result = ptr::null_mut();
+  let status =
+    unsafe { sys::napi_call_function(env, this, method, 0, ptr::null_mut(), &mut result) };
+  if status == sys::Status::napi_pending_exception {
+    let mut exception = ptr::null_mut();
+    unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+    return Err(unsafe { Error::from_js_exception(env, exception) });
+  }
+  check_status!(status, "Call iterator method failed")?;
+  Ok(result)
+}
+
+pub(crate) unsafe fn type_of(
+  env: sys::napi_env,
+  value: sys::napi_value,
+) -> Result<sys::napi_valuetype> {
+  let mut value_type = 0;
+  check_status!(unsafe { sys::napi_typeof(env, value, &mut value_type) })?;
+  Ok(value_type)
+}
@@ -0,0 +1,273 @@
+use std::collections::VecDeque;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::ptr;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use super::{type_of, AbortSignal, AsyncTask, FromNapiValue, ToNapiValue};
+use crate::{check_status, sys, Env, Error, JsError, ProgressTask, Result, Status, Task};
+
+/// The JavaScript function receiving the progress reported by a [`ProgressTask`],
+/// typed as `(progress: P) => void` in the `.d.ts` file.
+pub struct ProgressCallback<P> {
+  env: sys::napi_env,
+  callback: sys::napi_ref,
+  _progress: PhantomData<P>,
+}
+
+impl<P> FromNapiValue for ProgressCallback<P> {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+    if unsafe { type_of(env, napi_val) }? != sys::ValueType::napi_function {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The progress callback is not a function".to_owned(),
+      ));
+    }
+    let mut callback = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_create_reference(env, napi_val, 1, &mut callback) },
+      "Create reference of the progress callback failed"
+    )?;
+    Ok(Self {
+      env,
+      callback,
+      _progress: PhantomData,
+    })
+  }
+}
+
+impl<P> Drop for ProgressCallback<P> {
+  fn drop(&mut self) {
+    // it's only dropped on the JavaScript thread, see `ProgressState`
+    unsafe { sys::napi_delete_reference(self.env, self.callback) };
+  }
+}
+
+/// Handed to [`ProgressTask::compute`] to report the progress from the libuv thread pool.
+pub struct TaskContext<P: 'static> {
+  state: Arc<ProgressState<P>>,
+  tsfn: sys::napi_threadsafe_function,
+}
+
+unsafe impl<P: Send> Send for TaskContext<P> {}
+unsafe impl<P: Send> Sync for TaskContext<P> {}
+
+impl<P: ToNapiValue + Send + 'static> TaskContext<P> {
+  /// Deliver `progress` to the `onProgress` callback on the JavaScript thread.
+  ///
+  /// The progress is delivered in order, and all of it is delivered before the `Promise` of the task is settled.
+  pub fn report(&self, progress: P) {
+    let schedule = {
+      let mut queue = self
+        .state
+        .queue
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+      queue.push_back(progress);
+      // otherwise the queue is going to be flushed by the scheduled call
+      queue.len() == 1
+    };
+    if schedule {
+      unsafe {
+        sys::napi_call_threadsafe_function(
+          self.tsfn,
+          ptr::null_mut(),
+          sys::ThreadsafeFunctionCallMode::nonblocking,
+        )
+      };
+    }
+  }
+}
+
+impl<P: 'static> Drop for TaskContext<P> {
+  fn drop(&mut self) {
+    unsafe {
+      sys::napi_release_threadsafe_function(self.tsfn, sys::ThreadsafeFunctionReleaseMode::release)
+    };
+  }
+}
+
+struct ProgressState<P> {
+  callback: ProgressCallback<P>,
+  queue: Mutex<VecDeque<P>>,
+}
+
+// The callback is only called and dropped on the JavaScript thread,
+// the `Arc`s are held by the async work and the threadsafe function, which are both dropped there.
+unsafe impl<P: Send> Send for ProgressState<P> {}
+unsafe impl<P: Send> Sync for ProgressState<P> {}
+
+impl<P: ToNapiValue> ProgressState<P> {
+  /// Call the `onProgress` callback with the queued progress, the thrown JavaScript exception is turned into the `Err`
+  unsafe fn flush(&self, env: sys::napi_env) -> Result<()> {
+    let mut callback = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, self.callback.callback, &mut callback) },
+      "Get the progress callback failed"
+    )?;
+    let mut undefined = ptr::null_mut();
+    check_status!(unsafe { sys::napi_get_undefined(env, &mut undefined) })?;
+    loop {
+      let progress = match self
+        .queue
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .pop_front()
+      {
+        Some(progress) => progress,
+        None => return Ok(()),
+      };
+      let progress = unsafe { P::to_napi_value(env, progress) }?;
+      let status =
+        unsafe { sys::napi_call_function(env, undefined, callback, 1, &progress, ptr::null_mut()) };
+      if status == sys::Status::napi_pending_exception {
+        let mut exception = ptr::null_mut();
+        unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+        return Err(unsafe { Error::from_js_exception(env, exception) });
+      }
+      check_status!(status, "Call the progress callback failed")?;
+    }
+  }
+}
+
+/// Run a [`ProgressTask`] in the libuv thread pool, resolved into a `Promise` like [`AsyncTask`],
+/// while its progress is delivered to the `onProgress` callback.
+///
+/// ```rust,ignore
+/// #[napi]
+/// fn hash_file(path: String, on_progress: ProgressCallback<f64>) -> AsyncProgressTask<HashFile> {
+///   AsyncProgressTask::new(HashFile(path), on_progress)
+/// }
+/// ```
+pub struct AsyncProgressTask<T: ProgressTask> {
+  inner: T,
+  on_progress: ProgressCallback<T::Progress>,
+  abort_signal: Option<AbortSignal>,
+}
+
+impl<T: ProgressTask> AsyncProgressTask<T> {
+  pub fn new(task: T, on_progress: ProgressCallback<T::Progress>) -> Self {
+    Self {
+      inner: task,
+      on_progress,
+      abort_signal: None,
+    }
+  }
+
+  pub fn with_signal(
+    task: T,
+    on_progress: ProgressCallback<T::Progress>,
+    signal: AbortSignal,
+  ) -> Self {
+    Self {
+      inner: task,
+      on_progress,
+      abort_signal: Some(signal),
+    }
+  }
+}
+
+impl<T: ProgressTask> ToNapiValue for AsyncProgressTask<T> {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+    let state = Arc::new(ProgressState {
+      callback: val.on_progress,
+      queue: Mutex::new(VecDeque::new()),
+    });
+    let mut async_resource_name = ptr::null_mut();
+    let name = "napi_rs_progress_task";
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        name.as_ptr() as *const c_char,
+        name.len(),
+        &mut async_resource_name,
+      )
+    })?;
+    let context = Arc::into_raw(state.clone()) as *mut c_void;
+    let mut tsfn = ptr::null_mut();
+    let status = unsafe {
+      sys::napi_create_threadsafe_function(
+        env,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        async_resource_name,
+        0,
+        1,
+        context,
+        Some(progress_finalize_cb::<T::Progress>),
+        context,
+        Some(call_progress_cb::<T::Progress>),
+        &mut tsfn,
+      )
+    };
+    if status != sys::Status::napi_ok {
+      drop(unsafe { Arc::from_raw(context as *const ProgressState<T::Progress>) });
+    }
+    check_status!(
+      status,
+      "Create threadsafe function for the progress callback failed"
+    )?;
+    let task = ProgressTaskWork {
+      task: val.inner,
+      context: TaskContext { state, tsfn },
+    };
+    unsafe {
+      AsyncTask::to_napi_value(env, AsyncTask::with_optional_signal(task, val.abort_signal))
+    }
+  }
+}
+
+/// Adapt the [`ProgressTask`] into the [`Task`] executed by the async work
+struct ProgressTaskWork<T: ProgressTask> {
+  task: T,
+  context: TaskContext<T::Progress>,
+}
+
+impl<T: ProgressTask> Task for ProgressTaskWork<T> {
+  type Output = T::Output;
+  type JsValue = T::JsValue;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    self.task.compute(&self.context)
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    unsafe { self.context.state.flush(env.raw()) }?;
+    self.task.resolve(env, output)
+  }
+
+  fn reject(&mut self, env: Env, err: Error) -> Result<Self::JsValue> {
+    // the `Promise` is rejected with the error from `compute` rather than the one thrown by the callback
+    let _ = unsafe { self.context.state.flush(env.raw()) };
+    self.task.reject(env, err)
+  }
+
+  fn finally(&mut self, env: Env) -> Result<()> {
+    self.task.finally(env)
+  }
+}
+
+unsafe extern "C" fn call_progress_cb<P: ToNapiValue + 'static>(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  context: *mut c_void,
+  _data: *mut c_void,
+) {
+  // env is null if the environment is shutting down
+  if env.is_null() {
+    return;
+  }
+  let state = unsafe { &*(context as *const ProgressState<P>) };
+  if let Err(err) = unsafe { state.flush(env) } {
+    unsafe { JsError::from(err).throw_into(env) };
+  }
+}
+
+unsafe extern "C" fn progress_finalize_cb<P>(
+  _env: sys::napi_env,
+  finalize_data: *mut c_void,
+  _finalize_hint: *mut c_void,
+) {
+  drop(unsafe { Arc::from_raw(finalize_data as *const ProgressState<P>) });
+}
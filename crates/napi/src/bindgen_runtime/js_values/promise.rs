@@ -4,7 +4,7 @@ use std::pin::Pin;
 use std::ptr;
 use std::task::{Context, Poll};
 
-use tokio::sync::oneshot::{channel, Receiver, Sender};
+use futures_channel::oneshot::{channel, Receiver, Sender};
 
 use crate::{check_status, sys, Error, Result, Status};
 
@@ -79,6 +79,12 @@ impl<T: FromNapiValue> ValidateNapiValue for Promise<T> {
     }
     Ok(ptr::null_mut())
   }
+
+  unsafe fn is_valid(env: sys::napi_env, napi_val: sys::napi_value) -> bool {
+    let mut is_promise = false;
+    let status = unsafe { sys::napi_is_promise(env, napi_val, &mut is_promise) };
+    status == sys::Status::napi_ok && is_promise
+  }
 }
 
 unsafe impl<T: FromNapiValue> Send for Promise<T> {}
@@ -199,9 +205,10 @@ unsafe extern "C" fn then_callback<T: FromNapiValue>(
   );
   let resolve_value_t = Box::new(unsafe { T::from_napi_value(env, resolved_value[0]) });
   let sender = unsafe { Box::from_raw(data as *mut Sender<*mut Result<T>>) };
-  sender
-    .send(Box::into_raw(resolve_value_t))
-    .expect("Send Promise resolved value error");
+  // the `Promise` may be dropped before it's settled
+  if let Err(value) = sender.send(Box::into_raw(resolve_value_t)) {
+    drop(unsafe { Box::from_raw(value) });
+  }
   this
 }
 
@@ -228,16 +235,10 @@ unsafe extern "C" fn catch_callback<T: FromNapiValue>(
     "Get callback info from Promise::catch failed"
   );
   let rejected_value = rejected_value[0];
-  let mut error_ref = ptr::null_mut();
-  let create_ref_status =
-    unsafe { sys::napi_create_reference(env, rejected_value, 1, &mut error_ref) };
-  debug_assert!(
-    create_ref_status == sys::Status::napi_ok,
-    "Create Error reference failed"
-  );
+  let error = unsafe { Error::from_js_exception(env, rejected_value) };
   let sender = unsafe { Box::from_raw(data as *mut Sender<*mut Result<T>>) };
-  sender
-    .send(Box::into_raw(Box::new(Err(Error::from(error_ref)))))
-    .expect("Send Promise resolved value error");
+  if let Err(value) = sender.send(Box::into_raw(Box::new(Err(error)))) {
+    drop(unsafe { Box::from_raw(value) });
+  }
   this
 }
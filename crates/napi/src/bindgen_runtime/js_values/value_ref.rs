@@ -19,6 +19,8 @@ type RefInformation = (
 lazy_static! {
   pub(crate) static ref REFERENCE_MAP: PersistedSingleThreadHashMap<*mut c_void, RefInformation> =
     Default::default();
+  /// Count of the pending `async fn` calls borrowing the class instance, `-1` if borrowed by `&mut self`
+  static ref ASYNC_BORROWS: PersistedSingleThreadHashMap<*mut c_void, isize> = Default::default();
 }
 
 /// ### Experimental feature
@@ -279,3 +281,85 @@ impl<T: 'static, S: 'static> DerefMut for SharedReference<T, S> {
     unsafe { Box::leak(Box::from_raw(self.raw)) }
   }
 }
+
+/// Keep the class instance alive until the `async fn` borrowing `&self` or `&mut self` is settled.
+///
+/// Like `RefCell`, the `&mut self` async call is rejected while other async calls on the same instance are pending,
+/// and the `&self` async call is rejected while a `&mut self` async call is pending.
+/// The guard is dropped on the JavaScript thread after the `Promise` is settled.
+#[doc(hidden)]
+pub struct AsyncMethodGuard<T: 'static> {
+  _reference: Reference<T>,
+  raw: *mut c_void,
+}
+
+// The guard is only created and dropped on the JavaScript thread
+unsafe impl<T> Send for AsyncMethodGuard<T> {}
+unsafe impl<T> Sync for AsyncMethodGuard<T> {}
+
+impl<T: 'static> AsyncMethodGuard<T> {
+  /// # Safety
+  ///
+  /// `this_ptr` must be the wrapped value of the class instance
+  pub unsafe fn new(
+    env: crate::sys::napi_env,
+    this_ptr: *mut T,
+    is_mut: bool,
+    method_name: &str,
+  ) -> Result<Self> {
+    let raw = this_ptr as *mut c_void;
+    ASYNC_BORROWS.borrow_mut(|borrows| {
+      let count = borrows.entry(raw).or_insert(0);
+      match (*count, is_mut) {
+        (0, true) => {
+          *count = -1;
+          Ok(())
+        }
+        (c, false) if c >= 0 => {
+          *count += 1;
+          Ok(())
+        }
+        _ => Err(Error::new(
+          Status::GenericFailure,
+          format!(
+            "Can't call async method `{}` while {} on the same instance is pending",
+            method_name,
+            if is_mut {
+              "another async method"
+            } else {
+              "an async `&mut self` method"
+            }
+          ),
+        )),
+      }
+    })?;
+    match unsafe { Reference::<T>::from_value_ptr(raw, env) } {
+      Ok(reference) => Ok(Self {
+        _reference: reference,
+        raw,
+      }),
+      Err(e) => {
+        release_async_borrow(raw);
+        Err(e)
+      }
+    }
+  }
+}
+
+impl<T> Drop for AsyncMethodGuard<T> {
+  fn drop(&mut self) {
+    release_async_borrow(self.raw);
+  }
+}
+
+fn release_async_borrow(raw: *mut c_void) {
+  ASYNC_BORROWS.borrow_mut(|borrows| {
+    if let Some(count) = borrows.get_mut(&raw) {
+      if *count > 1 {
+        *count -= 1;
+      } else {
+        borrows.remove(&raw);
+      }
+    }
+  });
+}
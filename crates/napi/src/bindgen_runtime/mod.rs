@@ -2,19 +2,23 @@ use std::ffi::c_void;
 use std::mem;
 use std::rc::Rc;
 
+#[cfg(feature = "executor")]
+pub use async_iterator::{create_stream_iterator, AsyncGenerator, AsyncGeneratorFuture};
 pub use callback_info::*;
 pub use ctor::ctor;
 pub use env::*;
-pub use iterator::Generator;
+pub use iterator::{Generator, JsIterable};
 pub use js_values::*;
 pub use module_register::*;
 
 use super::sys;
 use crate::Status;
 
+#[cfg(feature = "executor")]
+pub mod async_iterator;
 mod callback_info;
 mod env;
-mod error;
+pub(crate) mod error;
 pub mod iterator;
 mod js_values;
 mod module_register;
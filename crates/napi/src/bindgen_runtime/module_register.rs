@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::ffi::CStr;
 use std::mem;
+use std::os::raw::c_char;
 use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicPtr};
 use std::sync::{atomic::Ordering, Mutex};
@@ -86,17 +87,59 @@ type ModuleClassProperty = PersistedSingleThreadHashMap<
 unsafe impl<K, V> Send for PersistedSingleThreadHashMap<K, V> {}
 unsafe impl<K, V> Sync for PersistedSingleThreadHashMap<K, V> {}
 
+type ModuleClassParent = PersistedSingleThreadHashMap<&'static str, &'static str>;
+
+/// The class of a `#[napi(error)]` enum variant
+struct ErrorVariantClass {
+  /// The Rust path of the variant, the class is registered by it since `js_name` may be shared by other enums
+  key: &'static str,
+  parent_js_name: &'static str,
+  js_name: &'static str,
+}
+
 type FnRegisterMap =
   PersistedSingleThreadHashMap<ExportRegisterCallback, (sys::napi_callback, &'static str)>;
 
+/// Check if the JavaScript arguments are acceptable by the overload
+pub type FnOverloadValidator = unsafe fn(sys::napi_env, &[sys::napi_value]) -> bool;
+
+#[doc(hidden)]
+pub struct FnOverload {
+  pub callback: unsafe extern "C" fn(sys::napi_env, sys::napi_callback_info) -> sys::napi_value,
+  pub validator: FnOverloadValidator,
+  /// Count of the JavaScript arguments, `Env` is excluded
+  pub args_len: usize,
+  /// TypeScript signature of the function, shown in the error message if no overload matches
+  pub signature: &'static str,
+  /// Source location of the overload, `file!()`, `line!()` and `column!()` of the `#[napi]` attribute
+  pub file: &'static str,
+  pub line: u32,
+  pub column: u32,
+}
+
+impl FnOverload {
+  /// The overloads are ordered by their declaration in the source,
+  /// which is stable across builds unlike the order the `ctor` functions run in.
+  fn declaration_order(&self) -> (&'static str, u32, u32) {
+    (self.file, self.line, self.column)
+  }
+}
+
+type FnOverloadMap =
+  PersistedSingleThreadHashMap<(Option<&'static str>, &'static str), &'static mut Vec<FnOverload>>;
+
 lazy_static! {
   static ref MODULE_REGISTER_CALLBACK: ModuleRegisterCallback = Default::default();
   static ref MODULE_CLASS_PROPERTIES: ModuleClassProperty = Default::default();
+  static ref MODULE_CLASS_PARENT: ModuleClassParent = Default::default();
+  static ref MODULE_ERROR_VARIANT_CLASSES: PersistedSingleThreadVec<ErrorVariantClass> =
+    Default::default();
   static ref MODULE_REGISTER_LOCK: Mutex<()> = Mutex::new(());
   static ref REGISTERED: AtomicBool = AtomicBool::new(false);
   static ref REGISTERED_CLASSES: thread_local::ThreadLocal<AtomicPtr<RegisteredClasses>> =
     thread_local::ThreadLocal::new();
   static ref FN_REGISTER_MAP: FnRegisterMap = Default::default();
+  static ref FN_OVERLOADS: FnOverloadMap = Default::default();
 }
 
 #[inline]
@@ -124,6 +167,41 @@ pub fn get_class_constructor(js_name: &'static str) -> Option<sys::napi_ref> {
   registered_classes.get(js_name).copied()
 }
 
+#[doc(hidden)]
+/// Validate that `napi_val` is an instance of the class registered as `js_name`,
+/// so the pointer it wraps can be recovered as that class.
+pub unsafe fn validate_class_instance(
+  env: sys::napi_env,
+  napi_val: sys::napi_value,
+  js_name: &'static str,
+) -> Result<sys::napi_value> {
+  let mut is_instance = false;
+  if let Some(ctor_ref) = get_class_constructor(js_name) {
+    let mut ctor = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, ctor_ref, &mut ctor) },
+      "Failed to get the constructor of class `{}`",
+      &js_name[..js_name.len() - 1],
+    )?;
+    check_status!(
+      unsafe { sys::napi_instanceof(env, napi_val, ctor, &mut is_instance) },
+      "Failed to check if value is an instance of class `{}`",
+      &js_name[..js_name.len() - 1],
+    )?;
+  }
+  if is_instance {
+    Ok(ptr::null_mut())
+  } else {
+    Err(crate::Error::new(
+      crate::Status::InvalidArg,
+      format!(
+        "Expect value to be an instance of class `{}`",
+        &js_name[..js_name.len() - 1]
+      ),
+    ))
+  }
+}
+
 #[doc(hidden)]
 #[cfg(feature = "compat-mode")]
 // compatibility for #[module_exports]
@@ -151,6 +229,139 @@ pub fn register_js_function(
   });
 }
 
+#[doc(hidden)]
+/// Register one of the Rust functions exported under the same `js_name` with `#[napi(overload)]`.
+/// The overloads are tried in the declaration order, the first one accepting the arguments is called.
+/// Overloads declared in different files are ordered by the file path.
+pub fn register_fn_overload(
+  js_mod: Option<&'static str>,
+  js_name: &'static str,
+  export_cb: ExportRegisterCallback,
+  overload: FnOverload,
+) {
+  FN_OVERLOADS.borrow_mut(|inner| match inner.get_mut(&(js_mod, js_name)) {
+    Some(overloads) => {
+      let index =
+        overloads.partition_point(|o| o.declaration_order() < overload.declaration_order());
+      overloads.insert(index, overload);
+    }
+    None => {
+      // The overloads are read by the dispatcher function until the process exits
+      inner.insert((js_mod, js_name), Box::leak(Box::new(vec![overload])));
+      register_module_export(js_mod, js_name, export_cb);
+    }
+  });
+}
+
+#[doc(hidden)]
+/// Create the JavaScript function which dispatches the call to the overloads registered by [`register_fn_overload`]
+pub unsafe fn create_fn_overload(
+  env: sys::napi_env,
+  js_mod: Option<&'static str>,
+  js_name: &'static str,
+) -> Result<sys::napi_value> {
+  let overloads = FN_OVERLOADS
+    .borrow_mut(|inner| {
+      inner
+        .get(&(js_mod, js_name))
+        .map(|overloads| &**overloads as *const Vec<FnOverload>)
+    })
+    .ok_or_else(|| {
+      crate::Error::new(
+        crate::Status::InvalidArg,
+        format!("No overload of function `{}` is registered", js_name),
+      )
+    })?;
+  let mut fn_ptr = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_create_function(
+        env,
+        js_name.as_ptr() as *const c_char,
+        js_name.len() - 1,
+        Some(call_fn_overload),
+        overloads as *mut std::ffi::c_void,
+        &mut fn_ptr,
+      )
+    },
+    "Failed to register function `{}`",
+    js_name,
+  )?;
+  Ok(fn_ptr)
+}
+
+unsafe extern "C" fn call_fn_overload(
+  env: sys::napi_env,
+  cb_info: sys::napi_callback_info,
+) -> sys::napi_value {
+  let mut argc = 0;
+  let mut data = ptr::null_mut();
+  let status = unsafe {
+    sys::napi_get_cb_info(
+      env,
+      cb_info,
+      &mut argc,
+      ptr::null_mut(),
+      ptr::null_mut(),
+      &mut data,
+    )
+  };
+  check_status_or_throw!(
+    env,
+    status,
+    "Failed to get the arguments of overloaded function"
+  );
+  let overloads = unsafe { &*(data as *const Vec<FnOverload>) };
+
+  // missing arguments are filled with `undefined`
+  let mut args_len = overloads
+    .iter()
+    .map(|overload| overload.args_len)
+    .max()
+    .unwrap_or(0)
+    .max(argc);
+  let mut args = vec![ptr::null_mut(); args_len];
+  let status = unsafe {
+    sys::napi_get_cb_info(
+      env,
+      cb_info,
+      &mut args_len,
+      args.as_mut_ptr(),
+      ptr::null_mut(),
+      ptr::null_mut(),
+    )
+  };
+  check_status_or_throw!(
+    env,
+    status,
+    "Failed to get the arguments of overloaded function"
+  );
+
+  for overload in overloads.iter() {
+    if argc <= overload.args_len && unsafe { (overload.validator)(env, &args[..overload.args_len]) }
+    {
+      return unsafe { (overload.callback)(env, cb_info) };
+    }
+  }
+
+  let signatures = overloads
+    .iter()
+    .map(|overload| format!("`{}`", overload.signature))
+    .collect::<Vec<_>>()
+    .join(", ");
+  unsafe {
+    crate::JsTypeError::from(crate::Error::new(
+      crate::Status::InvalidArg,
+      format!(
+        "Arguments do not match any overload of the function, expect one of {}",
+        signatures
+      ),
+    ))
+    .throw_into(env)
+  };
+  ptr::null_mut()
+}
+
 #[doc(hidden)]
 pub fn register_class(
   rust_name: &'static str,
@@ -166,6 +377,31 @@ pub fn register_class(
   });
 }
 
+#[doc(hidden)]
+/// Make the class `js_name` inherit from `parent_js_name`.
+/// `parent_js_name` could be either a registered class or a global constructor like `Error`.
+pub fn register_class_parent(js_name: &'static str, parent_js_name: &'static str) {
+  MODULE_CLASS_PARENT.borrow_mut(|inner| {
+    inner.insert(js_name, parent_js_name);
+  });
+}
+
+#[doc(hidden)]
+/// Register the class of the `#[napi(error)]` enum variant, it inherits the enum class `parent_js_name`
+/// and is exposed as its static property `js_name` instead of being exported, e.g. `ConfigError.NotFound`.
+/// The constructor is got by [`get_class_constructor`] with the Rust path `key`.
+pub fn register_error_variant_class(
+  key: &'static str,
+  parent_js_name: &'static str,
+  js_name: &'static str,
+) {
+  MODULE_ERROR_VARIANT_CLASSES.push(ErrorVariantClass {
+    key,
+    parent_js_name,
+    js_name,
+  });
+}
+
 #[inline]
 /// Get `JsFunction` from defined Rust `fn`
 /// ```rust
@@ -263,6 +499,9 @@ unsafe extern "C" fn napi_register_module_v1(
     sys::setup();
   }
   crate::__private::___CALL_FROM_FACTORY.get_or_default();
+  if let Err(e) = unsafe { crate::error::register_js_exception_releaser(env) } {
+    unsafe { JsError::from(e).throw_into(env) };
+  }
   let registered_classes_ptr = REGISTERED_CLASSES.get_or_default();
   let lock = MODULE_REGISTER_LOCK
     .lock()
@@ -346,6 +585,22 @@ unsafe extern "C" fn napi_register_module_v1(
     HashMap::with_capacity(MODULE_CLASS_PROPERTIES.borrow_mut(|inner| inner.len()));
 
   MODULE_CLASS_PROPERTIES.borrow_mut(|inner| {
+    let inherited_props = MODULE_CLASS_PARENT.borrow_mut(|parents| {
+      let props_by_class: HashMap<&'static str, &[Property]> = inner
+        .values()
+        .flat_map(|js_mods| js_mods.values())
+        .map(|(js_name, props)| (*js_name, props.as_slice()))
+        .collect();
+      parents
+        .keys()
+        .map(|js_name| {
+          (
+            *js_name,
+            get_inherited_properties(js_name, &props_by_class, parents),
+          )
+        })
+        .collect::<HashMap<_, _>>()
+    });
     inner.iter().for_each(|(rust_name, js_mods)| {
       for (js_mod, (js_name, props)) in js_mods {
         let mut exports_js_mod = ptr::null_mut();
@@ -383,7 +638,16 @@ unsafe extern "C" fn napi_register_module_v1(
           let (ctor, props): (Vec<_>, Vec<_>) = props.iter().partition(|prop| prop.is_ctor);
 
           let ctor = ctor.get(0).map(|c| c.raw().method.unwrap()).unwrap_or(noop);
-          let raw_props: Vec<_> = props.iter().map(|prop| prop.raw()).collect();
+          // Methods and accessors defined by `napi_define_class` could only be called on the instances of
+          // the class itself, so the parent class properties are defined on the derived class again.
+          let raw_props: Vec<_> = inherited_props
+            .get(js_name)
+            .into_iter()
+            .flatten()
+            .filter(|parent_prop| props.iter().all(|prop| prop.name != parent_prop.name))
+            .chain(props.iter().copied())
+            .map(|prop| prop.raw())
+            .collect();
 
           let js_class_name = CStr::from_bytes_with_nul_unchecked(js_name.as_bytes());
           let mut class_ptr = ptr::null_mut();
@@ -429,6 +693,22 @@ unsafe extern "C" fn napi_register_module_v1(
         }
       }
     });
+    MODULE_CLASS_PARENT.borrow_mut(|inner| {
+      for (js_name, parent_js_name) in inner.iter() {
+        if let Err(e) = unsafe { inherit_class(env, &registered_classes, js_name, parent_js_name) }
+        {
+          unsafe { JsError::from(e).throw_into(env) };
+        }
+      }
+    });
+    MODULE_ERROR_VARIANT_CLASSES.borrow_mut(|inner| {
+      for variant in inner.iter() {
+        if let Err(e) = unsafe { define_error_variant_class(env, &mut registered_classes, variant) }
+        {
+          unsafe { JsError::from(e).throw_into(env) };
+        }
+      }
+    });
     registered_classes_ptr.store(
       Box::into_raw(Box::new(registered_classes)),
       Ordering::Relaxed,
@@ -444,9 +724,11 @@ unsafe extern "C" fn napi_register_module_v1(
     })
   });
 
-  #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+  #[cfg(feature = "tokio_rt")]
   {
-    let _ = crate::tokio_runtime::RT.clone();
+    if crate::executor::is_tokio_executor() {
+      let _ = crate::tokio_runtime::RT.clone();
+    }
     crate::tokio_runtime::TOKIO_RT_REF_COUNT.fetch_add(1, Ordering::SeqCst);
     assert_eq!(
       unsafe {
@@ -464,6 +746,183 @@ unsafe extern "C" fn napi_register_module_v1(
   exports
 }
 
+/// Collect the instance properties of all the ancestors of the class `js_name`,
+/// properties of the closer ancestors override the farther ones.
+fn get_inherited_properties(
+  js_name: &'static str,
+  props_by_class: &HashMap<&'static str, &[Property]>,
+  parents: &HashMap<&'static str, &'static str>,
+) -> Vec<Property> {
+  let mut ancestors = vec![];
+  let mut current = js_name;
+  while let Some(parent) = parents.get(current) {
+    if ancestors.contains(parent) {
+      break;
+    }
+    ancestors.push(*parent);
+    current = parent;
+  }
+  let mut inherited: Vec<Property> = vec![];
+  for ancestor in ancestors.iter() {
+    for prop in props_by_class.get(ancestor).copied().unwrap_or_default() {
+      if !prop.is_ctor && !prop.is_static() && inherited.iter().all(|p| p.name != prop.name) {
+        inherited.push(prop.clone());
+      }
+    }
+  }
+  inherited
+}
+
+unsafe fn inherit_class(
+  env: sys::napi_env,
+  registered_classes: &RegisteredClasses,
+  js_name: &'static str,
+  parent_js_name: &'static str,
+) -> Result<()> {
+  let get_constructor = |name: &'static str| -> Result<sys::napi_value> {
+    let mut ctor = ptr::null_mut();
+    if let Some(ctor_ref) = registered_classes.get(name) {
+      check_status!(
+        unsafe { sys::napi_get_reference_value(env, *ctor_ref, &mut ctor) },
+        "Failed to get constructor of class `{}`",
+        name
+      )?;
+    } else {
+      let mut global = ptr::null_mut();
+      check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+      check_status!(
+        unsafe {
+          sys::napi_get_named_property(env, global, name.as_ptr() as *const c_char, &mut ctor)
+        },
+        "Failed to get global constructor `{}`",
+        name
+      )?;
+    }
+    Ok(ctor)
+  };
+  let get_prototype = |ctor: sys::napi_value| -> Result<sys::napi_value> {
+    let mut prototype = ptr::null_mut();
+    check_status!(unsafe {
+      sys::napi_get_named_property(
+        env,
+        ctor,
+        "prototype\0".as_ptr() as *const c_char,
+        &mut prototype,
+      )
+    })?;
+    Ok(prototype)
+  };
+  let ctor = get_constructor(js_name)?;
+  let parent_ctor = get_constructor(parent_js_name)?;
+
+  let mut global = ptr::null_mut();
+  let mut object = ptr::null_mut();
+  let mut set_prototype_of = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_global(env, &mut global) })?;
+  check_status!(unsafe {
+    sys::napi_get_named_property(
+      env,
+      global,
+      "Object\0".as_ptr() as *const c_char,
+      &mut object,
+    )
+  })?;
+  check_status!(unsafe {
+    sys::napi_get_named_property(
+      env,
+      object,
+      "setPrototypeOf\0".as_ptr() as *const c_char,
+      &mut set_prototype_of,
+    )
+  })?;
+  // Derived.prototype.__proto__ = Base.prototype
+  // Derived.__proto__ = Base
+  for (target, proto) in [
+    (get_prototype(ctor)?, get_prototype(parent_ctor)?),
+    (ctor, parent_ctor),
+  ] {
+    check_status!(
+      unsafe {
+        sys::napi_call_function(
+          env,
+          object,
+          set_prototype_of,
+          2,
+          [target, proto].as_ptr(),
+          ptr::null_mut(),
+        )
+      },
+      "Failed to inherit class `{}` from `{}`",
+      js_name,
+      parent_js_name
+    )?;
+  }
+  Ok(())
+}
+
+unsafe fn define_error_variant_class(
+  env: sys::napi_env,
+  registered_classes: &mut RegisteredClasses,
+  variant: &ErrorVariantClass,
+) -> Result<()> {
+  let js_name = variant.js_name;
+  let mut class_ptr = ptr::null_mut();
+  check_status!(
+    unsafe {
+      sys::napi_define_class(
+        env,
+        js_name.as_ptr() as *const c_char,
+        js_name.len() - 1,
+        Some(crate::bindgen_runtime::error::error_class_constructor),
+        ptr::null_mut(),
+        0,
+        ptr::null(),
+        &mut class_ptr,
+      )
+    },
+    "Failed to register Error class `{}`",
+    js_name,
+  )?;
+  let mut ctor_ref = ptr::null_mut();
+  check_status!(
+    unsafe { sys::napi_create_reference(env, class_ptr, 1, &mut ctor_ref) },
+    "Failed to reference Error class `{}`",
+    js_name,
+  )?;
+  registered_classes.insert(variant.key.to_owned(), ctor_ref);
+
+  let parent_ref = registered_classes
+    .get(variant.parent_js_name)
+    .ok_or_else(|| {
+      crate::Error::new(
+        crate::Status::InvalidArg,
+        format!(
+          "Failed to get the parent class `{}` of Error class `{}`",
+          variant.parent_js_name, js_name
+        ),
+      )
+    })?;
+  let mut parent_ctor = ptr::null_mut();
+  check_status!(unsafe { sys::napi_get_reference_value(env, *parent_ref, &mut parent_ctor) })?;
+  // not enumerable like the static members of the JavaScript classes
+  let property = sys::napi_property_descriptor {
+    utf8name: js_name.as_ptr() as *const c_char,
+    name: ptr::null_mut(),
+    method: None,
+    getter: None,
+    setter: None,
+    value: class_ptr,
+    attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+    data: ptr::null_mut(),
+  };
+  check_status!(
+    unsafe { sys::napi_define_properties(env, parent_ctor, 1, &property) },
+    "Failed to set Error class `{}` on its parent class",
+    js_name,
+  )?;
+  unsafe { inherit_class(env, registered_classes, variant.key, variant.parent_js_name) }
+}
+
 pub(crate) unsafe extern "C" fn noop(
   env: sys::napi_env,
   _info: sys::napi_callback_info,
@@ -1,13 +1,13 @@
 use std::any::TypeId;
 use std::convert::TryInto;
 use std::ffi::CString;
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+#[cfg(any(feature = "executor", feature = "uv"))]
 use std::future::Future;
 use std::mem;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
 
-#[cfg(all(feature = "napi4"))]
+#[cfg(any(feature = "napi4", feature = "uv"))]
 use crate::bindgen_runtime::ToNapiValue;
 use crate::{
   async_work::{self, AsyncWorkPromise},
@@ -996,7 +996,7 @@ impl Env {
 
   /// Run [Task](./trait.Task.html) in libuv thread pool, return [AsyncWorkPromise](./struct.AsyncWorkPromise.html)
   pub fn spawn<T: 'static + Task>(&self, task: T) -> Result<AsyncWorkPromise> {
-    async_work::run(self.0, task, None)
+    async_work::run(self.0, task)
   }
 
   pub fn run_in_scope<T, F>(&self, executor: F) -> Result<T>
@@ -1080,7 +1080,8 @@ impl Env {
     ThreadsafeFunction::create(self.0, func.0.value, max_queue_size, callback)
   }
 
-  #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+  /// Run the `Future` on the [`crate::executor::Executor`], which is the tokio runtime by default.
+  #[cfg(feature = "executor")]
   pub fn execute_tokio_future<
     T: 'static + Send,
     V: 'static + ToNapiValue,
@@ -1091,15 +1092,29 @@ impl Env {
     fut: F,
     resolver: R,
   ) -> Result<JsObject> {
-    use crate::tokio_runtime;
-
-    let promise = tokio_runtime::execute_tokio_future(self.0, fut, |env, val| unsafe {
+    let promise = crate::executor::execute_future(self.0, fut, |env, val| unsafe {
       resolver(&mut Env::from_raw(env), val).and_then(|v| ToNapiValue::to_napi_value(env, v))
     })?;
 
     Ok(unsafe { JsObject::from_raw_unchecked(self.0, promise) })
   }
 
+  /// Run the `Future` on the JavaScript thread, it's polled by the libuv event loop instead of the tokio runtime.
+  ///
+  /// The `Future` doesn't need to be `Send`, it could hold `Env` and `Ref` across `.await`.
+  /// The JavaScript values are only valid until the end of the poll they're created in, use a `Ref` to keep them across `.await`.
+  /// The returned `Promise` is settled with the output of the `Future`.
+  /// Use [`crate::uv::sleep`] instead of the blocking sleep or the tokio timers to wait in the `Future`.
+  #[cfg(feature = "uv")]
+  pub fn spawn_local<T, F>(&self, fut: F) -> Result<JsObject>
+  where
+    T: ToNapiValue,
+    F: 'static + Future<Output = Result<T>>,
+  {
+    let promise = crate::uv::spawn_local(self.0, fut)?;
+    Ok(unsafe { JsObject::from_raw_unchecked(self.0, promise) })
+  }
+
   /// This API does not observe leap seconds; they are ignored, as ECMAScript aligns with POSIX time specification.
   ///
   /// This API allocates a JavaScript Date object.
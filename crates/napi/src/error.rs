@@ -1,38 +1,297 @@
+#[cfg(feature = "backtrace")]
+use std::backtrace::{Backtrace, BacktraceStatus};
+#[cfg(feature = "napi4")]
+use std::collections::HashMap;
 use std::convert::{From, TryFrom};
 use std::error;
 use std::ffi::{CStr, CString};
 use std::fmt;
 #[cfg(feature = "serde-json")]
 use std::fmt::Display;
+use std::mem;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
-
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "napi4")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread::{self, ThreadId};
+
+#[cfg(feature = "napi4")]
+use lazy_static::lazy_static;
 #[cfg(feature = "serde-json")]
 use serde::{de, ser};
 #[cfg(feature = "serde-json")]
 use serde_json::Error as SerdeJSONError;
 
-use crate::{check_status, sys, Status};
+use crate::{bindgen_runtime::FromNapiValue, check_status, sys, type_of, Status, ValueType};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Represent `JsError`.
 /// Return this Error in `js_function`, **napi-rs** will throw it as `JsError` for you.
-/// If you want throw it as `TypeError` or `RangeError`, you can use `JsTypeError/JsRangeError::from(Error).throw_into(env)`
+/// If you want throw it as `TypeError` or `RangeError`, you can set the `kind` by `Error::with_kind`,
+/// or use `JsTypeError/JsRangeError::from(Error).throw_into(env)`
+///
+/// The `Error` converted from a JavaScript exception holds a reference to it, which could only be deleted on the JavaScript thread.
+/// If the `Error` is dropped on another thread, the reference is sent back to the JavaScript thread to be deleted,
+/// it's leaked instead if the `napi4` feature is disabled.
 #[derive(Debug, Clone)]
 pub struct Error {
   pub status: Status,
   pub reason: String,
-  // Convert raw `JsError` into Error
-  // Only be used in `async fn(p: Promise<T>)` scenario
-  #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-  pub(crate) maybe_raw: sys::napi_ref,
+  /// The constructor of the JavaScript error, `Error` by default
+  pub kind: ErrorKind,
+  /// The `code` property of the JavaScript error, fallback to the `status` if it's `None`
+  pub code: Option<String>,
+  /// The `cause` property of the JavaScript error
+  pub cause: Option<Box<Error>>,
+  // Create the JavaScript error from the original Rust error, see `#[napi(error)]`
+  pub(crate) value_creator: Option<ErrorValueCreator>,
+  // The original JavaScript exception, it will be rethrown as is
+  pub(crate) js_exception: Option<Arc<JsException>>,
+  // Where the `Error` is created on the native side
+  #[cfg(feature = "backtrace")]
+  pub(crate) backtrace: Arc<Backtrace>,
 }
 
 unsafe impl Send for Error {}
 unsafe impl Sync for Error {}
 
-impl error::Error for Error {}
+impl error::Error for Error {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    self
+      .cause
+      .as_deref()
+      .map(|cause| cause as &(dyn error::Error + 'static))
+  }
+}
+
+#[derive(Clone)]
+pub(crate) struct ErrorValueCreator(
+  Arc<dyn Fn(sys::napi_env) -> Result<sys::napi_value> + Send + Sync>,
+);
+
+const EXCEPTION_KEY: &str = "exception\0";
+
+/// Reference to the JavaScript value thrown from the JavaScript side
+pub(crate) struct JsException {
+  env: sys::napi_env,
+  raw: sys::napi_ref,
+  thread_id: ThreadId,
+  stack: Option<String>,
+}
+
+// The reference is only accessed on the JavaScript thread which created it
+unsafe impl Send for JsException {}
+unsafe impl Sync for JsException {}
+
+impl Drop for JsException {
+  fn drop(&mut self) {
+    // The exception could only be created after the module is registered, so the releaser is installed by then.
+    // It's called through the pointer to keep the Node-API calls out of the drop glue of `Error`,
+    // which is linked into the binaries running outside of Node.js, like `cargo test`.
+    let release = RELEASE_JS_EXCEPTION.load(Ordering::Acquire);
+    if !release.is_null() {
+      let release: ReleaseJsException = unsafe { mem::transmute(release) };
+      unsafe { release(self.env, self.raw, self.thread_id) };
+    }
+  }
+}
+
+type ReleaseJsException = unsafe fn(sys::napi_env, sys::napi_ref, ThreadId);
+
+static RELEASE_JS_EXCEPTION: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// The references dropped on the other threads, deleted by the threadsafe function on the JavaScript thread
+#[cfg(feature = "napi4")]
+struct ExceptionReleaser {
+  tsfn: sys::napi_threadsafe_function,
+  pending: Vec<sys::napi_ref>,
+}
+
+// The `napi_ref`s are only deleted on the JavaScript thread
+#[cfg(feature = "napi4")]
+unsafe impl Send for ExceptionReleaser {}
+
+#[cfg(feature = "napi4")]
+lazy_static! {
+  static ref EXCEPTION_RELEASERS: Mutex<HashMap<usize, ExceptionReleaser>> = Default::default();
+}
+
+/// Install the releaser of the JavaScript exceptions for the `env`, called when the module is registered.
+///
+/// Without the `napi4` feature, the exception dropped on the other threads is leaked.
+#[cfg_attr(not(feature = "napi4"), allow(unused_variables))]
+pub(crate) unsafe fn register_js_exception_releaser(env: sys::napi_env) -> Result<()> {
+  #[cfg(feature = "napi4")]
+  {
+    let mut async_resource_name = ptr::null_mut();
+    let name = "napi_rs_release_js_exception";
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        name.as_ptr() as *const c_char,
+        name.len(),
+        &mut async_resource_name,
+      )
+    })?;
+    let mut tsfn = ptr::null_mut();
+    check_status!(
+      unsafe {
+        sys::napi_create_threadsafe_function(
+          env,
+          ptr::null_mut(),
+          ptr::null_mut(),
+          async_resource_name,
+          0,
+          1,
+          ptr::null_mut(),
+          None,
+          ptr::null_mut(),
+          Some(release_pending_js_exceptions),
+          &mut tsfn,
+        )
+      },
+      "Create threadsafe function for releasing JavaScript exceptions failed"
+    )?;
+    // it shouldn't keep the event loop alive
+    check_status!(unsafe { sys::napi_unref_threadsafe_function(env, tsfn) })?;
+    let previous = lock_releasers().insert(
+      env as usize,
+      ExceptionReleaser {
+        tsfn,
+        pending: vec![],
+      },
+    );
+    if let Some(previous) = previous {
+      unsafe {
+        sys::napi_release_threadsafe_function(
+          previous.tsfn,
+          sys::ThreadsafeFunctionReleaseMode::release,
+        )
+      };
+    } else {
+      check_status!(unsafe {
+        sys::napi_add_env_cleanup_hook(
+          env,
+          Some(unregister_js_exception_releaser),
+          env as *mut c_void,
+        )
+      })?;
+    }
+  }
+  RELEASE_JS_EXCEPTION.store(release_js_exception as *mut (), Ordering::Release);
+  Ok(())
+}
+
+#[cfg(feature = "napi4")]
+fn lock_releasers() -> MutexGuard<'static, HashMap<usize, ExceptionReleaser>> {
+  EXCEPTION_RELEASERS
+    .lock()
+    .unwrap_or_else(PoisonError::into_inner)
+}
+
+unsafe fn release_js_exception(env: sys::napi_env, raw: sys::napi_ref, thread_id: ThreadId) {
+  #[cfg(feature = "napi4")]
+  {
+    let mut releasers = lock_releasers();
+    // the reference is already freed along with the `env`
+    let releaser = match releasers.get_mut(&(env as usize)) {
+      Some(releaser) => releaser,
+      None => return,
+    };
+    if thread::current().id() != thread_id {
+      releaser.pending.push(raw);
+      // otherwise the pending references are going to be deleted by the scheduled call
+      if releaser.pending.len() == 1 {
+        unsafe {
+          sys::napi_call_threadsafe_function(
+            releaser.tsfn,
+            ptr::null_mut(),
+            sys::ThreadsafeFunctionCallMode::nonblocking,
+          )
+        };
+      }
+      return;
+    }
+  }
+  #[cfg(not(feature = "napi4"))]
+  if thread::current().id() != thread_id {
+    return;
+  }
+  let status = unsafe { sys::napi_delete_reference(env, raw) };
+  debug_assert!(
+    status == sys::Status::napi_ok,
+    "Delete JavaScript exception reference failed"
+  );
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn release_pending_js_exceptions(
+  env: sys::napi_env,
+  _js_callback: sys::napi_value,
+  _context: *mut c_void,
+  _data: *mut c_void,
+) {
+  // env is null if the environment is shutting down
+  if env.is_null() {
+    return;
+  }
+  let pending = match lock_releasers().get_mut(&(env as usize)) {
+    Some(releaser) => mem::take(&mut releaser.pending),
+    None => return,
+  };
+  for raw in pending {
+    unsafe { sys::napi_delete_reference(env, raw) };
+  }
+}
+
+#[cfg(feature = "napi4")]
+unsafe extern "C" fn unregister_js_exception_releaser(env: *mut c_void) {
+  let env = env as sys::napi_env;
+  if let Some(releaser) = lock_releasers().remove(&(env as usize)) {
+    for raw in releaser.pending {
+      unsafe { sys::napi_delete_reference(env, raw) };
+    }
+    unsafe {
+      sys::napi_release_threadsafe_function(
+        releaser.tsfn,
+        sys::ThreadsafeFunctionReleaseMode::release,
+      )
+    };
+  }
+}
+
+impl fmt::Debug for JsException {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("JsException")
+      .field("stack", &self.stack)
+      .finish()
+  }
+}
+
+impl fmt::Debug for ErrorValueCreator {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("ErrorValueCreator")
+  }
+}
+
+/// The JavaScript error constructor used to throw the `Error`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+  Error,
+  TypeError,
+  RangeError,
+  #[cfg(feature = "experimental")]
+  SyntaxError,
+}
+
+impl Default for ErrorKind {
+  fn default() -> Self {
+    ErrorKind::Error
+  }
+}
 
 impl From<std::convert::Infallible> for Error {
   fn from(_: std::convert::Infallible) -> Self {
@@ -61,17 +320,6 @@ impl From<SerdeJSONError> for Error {
   }
 }
 
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-impl From<sys::napi_ref> for Error {
-  fn from(value: sys::napi_ref) -> Self {
-    Self {
-      status: Status::InvalidArg,
-      reason: "".to_string(),
-      maybe_raw: value,
-    }
-  }
-}
-
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     if !self.reason.is_empty() {
@@ -87,8 +335,13 @@ impl Error {
     Error {
       status,
       reason,
-      #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-      maybe_raw: ptr::null_mut(),
+      kind: ErrorKind::Error,
+      code: None,
+      cause: None,
+      value_creator: None,
+      js_exception: None,
+      #[cfg(feature = "backtrace")]
+      backtrace: Arc::new(Backtrace::capture()),
     }
   }
 
@@ -96,8 +349,13 @@ impl Error {
     Error {
       status,
       reason: "".to_owned(),
-      #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-      maybe_raw: ptr::null_mut(),
+      kind: ErrorKind::Error,
+      code: None,
+      cause: None,
+      value_creator: None,
+      js_exception: None,
+      #[cfg(feature = "backtrace")]
+      backtrace: Arc::new(Backtrace::capture()),
     }
   }
 
@@ -105,10 +363,118 @@ impl Error {
     Error {
       status: Status::GenericFailure,
       reason: reason.into(),
-      #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-      maybe_raw: ptr::null_mut(),
+      kind: ErrorKind::Error,
+      code: None,
+      cause: None,
+      value_creator: None,
+      js_exception: None,
+      #[cfg(feature = "backtrace")]
+      backtrace: Arc::new(Backtrace::capture()),
     }
   }
+
+  /// Set the constructor of the JavaScript error
+  pub fn with_kind(mut self, kind: ErrorKind) -> Self {
+    self.kind = kind;
+    self
+  }
+
+  /// Set the `code` property of the JavaScript error
+  pub fn with_code<T: Into<String>>(mut self, code: T) -> Self {
+    self.code = Some(code.into());
+    self
+  }
+
+  /// Set the `cause` property of the JavaScript error
+  pub fn with_cause<E: Into<Error>>(mut self, cause: E) -> Self {
+    self.cause = Some(Box::new(cause.into()));
+    self
+  }
+
+  /// Take the pending JavaScript exception, e.g. thrown by a JavaScript function called from Rust.
+  ///
+  /// The `reason` is the `message` of the exception, and the exception is rethrown as is
+  /// when this `Error` is returned to JavaScript.
+  ///
+  /// # Safety
+  ///
+  /// `env` must be a valid `napi_env` of the current thread.
+  pub unsafe fn from_pending_exception(env: sys::napi_env) -> Self {
+    let mut exception = ptr::null_mut();
+    let status = unsafe { sys::napi_get_and_clear_last_exception(env, &mut exception) };
+    if status != sys::Status::napi_ok || exception.is_null() {
+      return Error::from_status(Status::PendingException);
+    }
+    unsafe { Error::from_js_exception(env, exception) }
+  }
+
+  /// Own the JavaScript value thrown or rejected from the JavaScript side
+  pub(crate) unsafe fn from_js_exception(env: sys::napi_env, exception: sys::napi_value) -> Self {
+    let (reason, stack) = unsafe { get_exception_message_and_stack(env, exception) };
+    // Only objects could be referenced in the older Node-API versions,
+    // so the exception is held by an object in case it's a primitive value
+    let mut holder = ptr::null_mut();
+    let mut raw = ptr::null_mut();
+    let held = check_status!(unsafe { sys::napi_create_object(env, &mut holder) })
+      .and_then(|_| {
+        check_status!(unsafe {
+          sys::napi_set_named_property(
+            env,
+            holder,
+            EXCEPTION_KEY.as_ptr() as *const c_char,
+            exception,
+          )
+        })
+      })
+      .and_then(|_| check_status!(unsafe { sys::napi_create_reference(env, holder, 1, &mut raw) }));
+    match held {
+      Ok(()) => {
+        let mut err = Error::new(Status::PendingException, reason);
+        err.js_exception = Some(Arc::new(JsException {
+          env,
+          raw,
+          thread_id: thread::current().id(),
+          stack,
+        }));
+        err
+      }
+      Err(_) => Error::new(Status::GenericFailure, reason),
+    }
+  }
+
+  /// The native backtrace captured when the `Error` was created.
+  ///
+  /// It's only captured if the `RUST_LIB_BACKTRACE` or `RUST_BACKTRACE` environment variable is set,
+  /// and exposed as the `nativeStack` property of the JavaScript error.
+  ///
+  /// The `backtrace` feature requires Rust 1.65 or later.
+  #[cfg(feature = "backtrace")]
+  pub fn backtrace(&self) -> &Backtrace {
+    &self.backtrace
+  }
+
+  /// If this `Error` owns an exception thrown from JavaScript
+  pub fn is_js_exception(&self) -> bool {
+    self.js_exception.is_some()
+  }
+
+  /// The `stack` of the exception thrown from JavaScript
+  pub fn js_stack(&self) -> Option<&str> {
+    self
+      .js_exception
+      .as_ref()
+      .and_then(|exception| exception.stack.as_deref())
+  }
+
+  /// Create the JavaScript error by `creator` instead of `kind`, `code` and `cause`
+  #[doc(hidden)]
+  pub fn with_value_creator<F>(mut self, creator: F) -> Self
+  where
+    F: 'static + Send + Sync + Fn(sys::napi_env) -> Result<sys::napi_value>,
+  {
+    self.value_creator = Some(ErrorValueCreator(Arc::new(creator)));
+    self
+  }
 }
 
 impl From<std::ffi::NulError> for Error {
@@ -116,8 +482,13 @@ impl From<std::ffi::NulError> for Error {
     Error {
       status: Status::GenericFailure,
       reason: format!("{}", error),
-      #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-      maybe_raw: ptr::null_mut(),
+      kind: ErrorKind::Error,
+      code: None,
+      cause: None,
+      value_creator: None,
+      js_exception: None,
+      #[cfg(feature = "backtrace")]
+      backtrace: Arc::new(Backtrace::capture()),
     }
   }
 }
@@ -127,8 +498,13 @@ impl From<std::io::Error> for Error {
     Error {
       status: Status::GenericFailure,
       reason: format!("{}", error),
-      #[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-      maybe_raw: ptr::null_mut(),
+      kind: ErrorKind::Error,
+      code: None,
+      cause: None,
+      value_creator: None,
+      js_exception: None,
+      #[cfg(feature = "backtrace")]
+      backtrace: Arc::new(Backtrace::capture()),
     }
   }
 }
@@ -167,32 +543,168 @@ pub struct JsRangeError(Error);
 #[cfg(feature = "experimental")]
 pub struct JsSyntaxError(Error);
 
+pub(crate) unsafe fn create_js_error(
+  env: sys::napi_env,
+  kind: ErrorKind,
+  err: &Error,
+) -> Result<sys::napi_value> {
+  if let Some(exception) = err.js_exception.as_ref().filter(|e| e.env == env) {
+    let mut holder = ptr::null_mut();
+    let mut value = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_reference_value(env, exception.raw, &mut holder) },
+      "Failed to get the JavaScript exception from reference"
+    )?;
+    check_status!(unsafe {
+      sys::napi_get_named_property(
+        env,
+        holder,
+        EXCEPTION_KEY.as_ptr() as *const c_char,
+        &mut value,
+      )
+    })?;
+    return Ok(value);
+  }
+  if let Some(creator) = &err.value_creator {
+    let js_error = (creator.0)(env)?;
+    #[cfg(feature = "backtrace")]
+    unsafe { set_native_stack(env, js_error, err) }?;
+    return Ok(js_error);
+  }
+  let error_code = err
+    .code
+    .clone()
+    .unwrap_or_else(|| format!("{:?}", err.status));
+  let code_len = error_code.len();
+  let error_code = CString::new(error_code)?;
+  let reason_len = err.reason.len();
+  let reason = CString::new(err.reason.as_str())?;
+  let mut code_string = ptr::null_mut();
+  let mut reason_string = ptr::null_mut();
+  let mut js_error = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_create_string_utf8(env, error_code.as_ptr(), code_len, &mut code_string)
+  })?;
+  check_status!(unsafe {
+    sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
+  })?;
+  check_status!(unsafe {
+    match kind {
+      ErrorKind::Error => sys::napi_create_error(env, code_string, reason_string, &mut js_error),
+      ErrorKind::TypeError => {
+        sys::napi_create_type_error(env, code_string, reason_string, &mut js_error)
+      }
+      ErrorKind::RangeError => {
+        sys::napi_create_range_error(env, code_string, reason_string, &mut js_error)
+      }
+      #[cfg(feature = "experimental")]
+      ErrorKind::SyntaxError => {
+        sys::node_api_create_syntax_error(env, code_string, reason_string, &mut js_error)
+      }
+    }
+  })?;
+  if let Some(cause) = &err.cause {
+    let cause_value = unsafe { create_js_error(env, cause.kind, cause) }?;
+    let cause_key = unsafe { CStr::from_bytes_with_nul_unchecked(b"cause\0") };
+    check_status!(unsafe {
+      sys::napi_set_named_property(env, js_error, cause_key.as_ptr(), cause_value)
+    })?;
+  }
+  #[cfg(feature = "backtrace")]
+  unsafe { set_native_stack(env, js_error, err) }?;
+  Ok(js_error)
+}
+
+/// Expose the native backtrace as the `nativeStack` property, it's not enumerable like the `stack`
+#[cfg(feature = "backtrace")]
+unsafe fn set_native_stack(
+  env: sys::napi_env,
+  js_error: sys::napi_value,
+  err: &Error,
+) -> Result<()> {
+  if err.backtrace.status() != BacktraceStatus::Captured {
+    return Ok(());
+  }
+  let native_stack = err.backtrace.to_string();
+  let mut value = ptr::null_mut();
+  check_status!(unsafe {
+    sys::napi_create_string_utf8(
+      env,
+      native_stack.as_ptr() as *const c_char,
+      native_stack.len(),
+      &mut value,
+    )
+  })?;
+  let property = sys::napi_property_descriptor {
+    utf8name: "nativeStack\0".as_ptr() as *const c_char,
+    name: ptr::null_mut(),
+    method: None,
+    getter: None,
+    setter: None,
+    value,
+    attributes: sys::PropertyAttributes::writable | sys::PropertyAttributes::configurable,
+    data: ptr::null_mut(),
+  };
+  check_status!(unsafe { sys::napi_define_properties(env, js_error, 1, &property) })
+}
+
+/// `message` and `stack` of the JavaScript exception, `message` is the exception itself if it's not an object
+unsafe fn get_exception_message_and_stack(
+  env: sys::napi_env,
+  exception: sys::napi_value,
+) -> (String, Option<String>) {
+  let get_string = |value: sys::napi_value| -> Option<String> {
+    let mut string = ptr::null_mut();
+    if unsafe { sys::napi_coerce_to_string(env, value, &mut string) } != sys::Status::napi_ok {
+      // `toString` of the exception throws
+      let mut ignored = ptr::null_mut();
+      unsafe { sys::napi_get_and_clear_last_exception(env, &mut ignored) };
+      return None;
+    }
+    unsafe { String::from_napi_value(env, string) }.ok()
+  };
+  let get_property = |key: &[u8]| -> Option<String> {
+    let mut value = ptr::null_mut();
+    let key = unsafe { CStr::from_bytes_with_nul_unchecked(key) };
+    check_status!(unsafe {
+      sys::napi_get_named_property(env, exception, key.as_ptr(), &mut value)
+    })
+    .ok()?;
+    match type_of!(env, value) {
+      Ok(ValueType::Undefined) | Err(_) => None,
+      Ok(_) => get_string(value),
+    }
+  };
+  match type_of!(env, exception) {
+    Ok(ValueType::Object) | Ok(ValueType::Function) => (
+      get_property(b"message\0")
+        .or_else(|| get_string(exception))
+        .unwrap_or_default(),
+      get_property(b"stack\0"),
+    ),
+    _ => (get_string(exception).unwrap_or_default(), None),
+  }
+}
+
 macro_rules! impl_object_methods {
-  ($js_value:ident, $kind:expr) => {
+  ($js_value:ident, $err:ident => $kind:expr) => {
     impl $js_value {
+      fn kind(&self) -> ErrorKind {
+        let $err = &self.0;
+        $kind
+      }
+
       /// # Safety
       ///
       /// This function is safety if env is not null ptr.
       pub unsafe fn into_value(self, env: sys::napi_env) -> sys::napi_value {
-        let error_status = format!("{:?}", self.0.status);
-        let status_len = error_status.len();
-        let error_code_string = CString::new(error_status).unwrap();
-        let reason_len = self.0.reason.len();
-        let reason = CString::new(self.0.reason).unwrap();
-        let mut error_code = ptr::null_mut();
-        let mut reason_string = ptr::null_mut();
-        let mut js_error = ptr::null_mut();
-        let create_code_status = unsafe {
-          sys::napi_create_string_utf8(env, error_code_string.as_ptr(), status_len, &mut error_code)
-        };
-        debug_assert!(create_code_status == sys::Status::napi_ok);
-        let create_reason_status = unsafe {
-          sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
-        };
-        debug_assert!(create_reason_status == sys::Status::napi_ok);
-        let create_error_status = unsafe { $kind(env, error_code, reason_string, &mut js_error) };
-        debug_assert!(create_error_status == sys::Status::napi_ok);
-        js_error
+        let js_error = unsafe { create_js_error(env, self.kind(), &self.0) };
+        debug_assert!(
+          js_error.is_ok(),
+          "Create JavaScript error failed: {:?}",
+          js_error
+        );
+        js_error.unwrap_or_else(|_| ptr::null_mut())
       }
 
       /// # Safety
@@ -202,7 +714,8 @@ macro_rules! impl_object_methods {
         #[cfg(debug_assertions)]
         let reason = self.0.reason.clone();
         let status = self.0.status;
-        if status == Status::PendingException {
+        // The exception is still pending if it's not taken by `Error::from_pending_exception`
+        if status == Status::PendingException && self.0.js_exception.is_none() {
           return;
         }
         let js_error = unsafe { self.into_value(env) };
@@ -221,23 +734,7 @@ macro_rules! impl_object_methods {
 
       #[allow(clippy::not_unsafe_ptr_arg_deref)]
       pub fn throw(&self, env: sys::napi_env) -> Result<()> {
-        let error_status = format!("{:?}\0", self.0.status);
-        let status_len = error_status.len();
-        let error_code_string =
-          unsafe { CStr::from_bytes_with_nul_unchecked(error_status.as_bytes()) };
-        let reason_len = self.0.reason.len();
-        let reason_c_string = format!("{}\0", self.0.reason.clone());
-        let reason = unsafe { CStr::from_bytes_with_nul_unchecked(reason_c_string.as_bytes()) };
-        let mut error_code = ptr::null_mut();
-        let mut reason_string = ptr::null_mut();
-        let mut js_error = ptr::null_mut();
-        check_status!(unsafe {
-          sys::napi_create_string_utf8(env, error_code_string.as_ptr(), status_len, &mut error_code)
-        })?;
-        check_status!(unsafe {
-          sys::napi_create_string_utf8(env, reason.as_ptr(), reason_len, &mut reason_string)
-        })?;
-        check_status!(unsafe { $kind(env, error_code, reason_string, &mut js_error) })?;
+        let js_error = unsafe { create_js_error(env, self.kind(), &self.0) }?;
         check_status!(unsafe { sys::napi_throw(env, js_error) })
       }
     }
@@ -250,11 +747,11 @@ macro_rules! impl_object_methods {
   };
 }
 
-impl_object_methods!(JsError, sys::napi_create_error);
-impl_object_methods!(JsTypeError, sys::napi_create_type_error);
-impl_object_methods!(JsRangeError, sys::napi_create_range_error);
+impl_object_methods!(JsError, err => err.kind);
+impl_object_methods!(JsTypeError, _err => ErrorKind::TypeError);
+impl_object_methods!(JsRangeError, _err => ErrorKind::RangeError);
 #[cfg(feature = "experimental")]
-impl_object_methods!(JsSyntaxError, sys::node_api_create_syntax_error);
+impl_object_methods!(JsSyntaxError, _err => ErrorKind::SyntaxError);
 
 #[doc(hidden)]
 #[macro_export]
@@ -283,3 +780,31 @@ macro_rules! check_status {
     }
   }};
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! check_pending_exception {
+  ($env:expr, $code:expr) => {{
+    let env = $env;
+    let c = $code;
+    match c {
+      $crate::sys::Status::napi_ok => Ok(()),
+      $crate::sys::Status::napi_pending_exception => {
+        Err(unsafe { $crate::Error::from_pending_exception(env) })
+      }
+      _ => Err($crate::Error::new($crate::Status::from(c), "".to_owned())),
+    }
+  }};
+
+  ($env:expr, $code:expr, $($msg:tt)*) => {{
+    let env = $env;
+    let c = $code;
+    match c {
+      $crate::sys::Status::napi_ok => Ok(()),
+      $crate::sys::Status::napi_pending_exception => {
+        Err(unsafe { $crate::Error::from_pending_exception(env) })
+      }
+      _ => Err($crate::Error::new($crate::Status::from(c), format!($($msg)*))),
+    }
+  }};
+}
@@ -0,0 +1,136 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+use lazy_static::lazy_static;
+
+#[cfg(feature = "tokio_rt")]
+pub use crate::tokio_runtime::TokioExecutor;
+use crate::{check_status, promise, sys, Error, Result, Status};
+
+/// The `Future` handed to the [`Executor`], it's resolved into the JavaScript `Promise` by itself.
+pub type SpawnedFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Runs the futures of `#[napi] async fn`, `Env::execute_tokio_future` and the async iterators.
+///
+/// The futures are `Send` and may be polled on any thread, `Promise<T>` and the other async bindings don't rely on
+/// the tokio runtime, so any executor (`async-std`, `smol`, a single-threaded one) could be used.
+/// With the `tokio_rt` feature the tokio runtime is the default, otherwise the `executor` feature
+/// requires an executor to be set by [`set_executor`].
+pub trait Executor: Send + Sync + 'static {
+  fn spawn(&self, fut: SpawnedFuture);
+}
+
+impl<F> Executor for F
+where
+  F: Fn(SpawnedFuture) + Send + Sync + 'static,
+{
+  fn spawn(&self, fut: SpawnedFuture) {
+    self(fut)
+  }
+}
+
+lazy_static! {
+  static ref EXECUTOR: RwLock<Option<Box<dyn Executor>>> = RwLock::new(None);
+}
+
+static EXECUTOR_USED: AtomicBool = AtomicBool::new(false);
+
+/// Replace the tokio runtime with another [`Executor`], or provide one without the `tokio_rt` feature.
+///
+/// Must be called before any `Future` is spawned, typically in the `#[napi::module_init]` function:
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   napi::executor::set_executor(|fut: napi::executor::SpawnedFuture| {
+///     async_std::task::spawn(fut);
+///   })
+///   .expect("Set executor failed");
+/// }
+/// ```
+pub fn set_executor<E: Executor>(executor: E) -> Result<()> {
+  let mut current = EXECUTOR
+    .write()
+    .map_err(|_| Error::new(Status::GenericFailure, "Lock executor failed".to_owned()))?;
+  if EXECUTOR_USED.load(Ordering::SeqCst) {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "The executor is already in use, it must be set in the `#[napi::module_init]` function"
+        .to_owned(),
+    ));
+  }
+  *current = Some(Box::new(executor));
+  Ok(())
+}
+
+/// The tokio runtime isn't started if another [`Executor`] is set
+#[cfg(feature = "tokio_rt")]
+pub(crate) fn is_tokio_executor() -> bool {
+  EXECUTOR.read().map_or(true, |executor| executor.is_none())
+}
+
+#[cfg(feature = "tokio_rt")]
+fn default_executor() -> Result<&'static dyn Executor> {
+  Ok(&TokioExecutor)
+}
+
+#[cfg(not(feature = "tokio_rt"))]
+fn default_executor() -> Result<&'static dyn Executor> {
+  Err(Error::new(
+    Status::GenericFailure,
+    "No executor is set, call `napi::executor::set_executor` in the `#[napi::module_init]` function"
+      .to_owned(),
+  ))
+}
+
+/// Call `f` with the [`Executor`] set by [`set_executor`], or the default one
+fn with_executor<R>(f: impl FnOnce(&dyn Executor) -> R) -> Result<R> {
+  EXECUTOR_USED.store(true, Ordering::SeqCst);
+  let executor = EXECUTOR.read().unwrap_or_else(PoisonError::into_inner);
+  match executor.as_deref() {
+    Some(executor) => Ok(f(executor)),
+    None => default_executor().map(f),
+  }
+}
+
+/// Spawn the `Future` onto the [`Executor`], which is the tokio runtime by default
+///
+/// # Panics
+///
+/// If there is no [`Executor`], see [`set_executor`].
+pub fn spawn<F>(fut: F)
+where
+  F: 'static + Send + Future<Output = ()>,
+{
+  if let Err(e) = with_executor(|executor| executor.spawn(Box::pin(fut))) {
+    panic!("{}", e.reason);
+  }
+}
+
+/// Run the `Future` on the [`Executor`], the returned `Promise` is settled with its output converted by `resolver`
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn execute_future<
+  Data: 'static + Send,
+  Fut: 'static + Send + Future<Output = Result<Data>>,
+  Resolver: 'static + Send + Sync + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
+>(
+  env: sys::napi_env,
+  fut: Fut,
+  resolver: Resolver,
+) -> Result<sys::napi_value> {
+  with_executor(|executor| {
+    let mut promise = ptr::null_mut();
+    let mut deferred = ptr::null_mut();
+
+    check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) })?;
+
+    let future_promise = promise::FuturePromise::new(env, deferred, resolver)?;
+    let future_to_resolve = promise::resolve_from_future(future_promise.start()?, fut);
+    executor.spawn(Box::pin(future_to_resolve));
+
+    Ok(promise)
+  })?
+}
@@ -41,6 +41,11 @@
 //! NAPI_RS_TOKIO_CHANNEL_BUFFER_SIZE=1000 node ./app.js
 //! ```
 //!
+//! ### executor
+//! `async fn`, `Promise<T>` and the async iterators without the tokio runtime,
+//! the futures are spawned onto the executor set by `napi::executor::set_executor`.
+//! It's enabled by `tokio_rt`, which makes the tokio runtime the default executor.
+//!
 //! ### latin1
 //!
 //! Decode latin1 string from JavaScript using [encoding_rs](https://docs.rs/encoding_rs).
@@ -84,13 +89,18 @@ mod call_context;
 mod cleanup_env;
 mod env;
 mod error;
+#[cfg(feature = "executor")]
+pub mod executor;
 mod js_values;
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+mod panic;
+#[cfg(feature = "executor")]
 mod promise;
 mod status;
 mod task;
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
-mod tokio_runtime;
+#[cfg(feature = "tokio_rt")]
+pub mod tokio_runtime;
+#[cfg(feature = "uv")]
+pub mod uv;
 mod value_type;
 #[cfg(feature = "napi3")]
 pub use cleanup_env::CleanupEnvHook;
@@ -109,8 +119,10 @@ pub use env::*;
 pub use error::*;
 pub use js_values::*;
 pub use status::Status;
+#[cfg(feature = "napi4")]
+pub use task::ProgressTask;
 pub use task::Task;
-#[cfg(all(feature = "tokio_rt", feature = "napi4"))]
+#[cfg(feature = "tokio_rt")]
 pub use tokio_runtime::shutdown_tokio_rt;
 pub use value_type::*;
 pub use version::NodeVersion;
@@ -156,19 +168,32 @@ pub use crate::bindgen_runtime::ctor as module_init;
 pub mod bindgen_prelude {
   #[cfg(feature = "compat-mode")]
   pub use crate::bindgen_runtime::register_module_exports;
+  #[cfg(feature = "executor")]
+  pub use crate::executor::{execute_future, spawn};
   #[cfg(feature = "tokio_rt")]
-  pub use crate::tokio_runtime::*;
+  pub use crate::tokio_runtime::{execute_tokio_future, shutdown_tokio_rt};
+  #[cfg(feature = "napi4")]
+  pub use crate::ProgressTask;
   pub use crate::{
-    assert_type_of, bindgen_runtime::*, check_status, check_status_or_throw, error, error::*, sys,
-    type_of, JsError, Property, PropertyAttributes, Result, Status, Task, ValueType,
+    assert_type_of, bindgen_runtime::*, check_pending_exception, check_status,
+    check_status_or_throw, error, error::*, sys, type_of, JsError, Property, PropertyAttributes,
+    Result, Status, Task, ValueType,
   };
 }
 
 #[doc(hidden)]
 pub mod __private {
+  #[cfg(feature = "executor")]
+  pub use crate::bindgen_runtime::async_iterator::create_async_iterator;
   pub use crate::bindgen_runtime::{
-    get_class_constructor, iterator::create_iterator, register_class, ___CALL_FROM_FACTORY,
+    create_fn_overload,
+    error::{error_class_constructor, new_error_instance},
+    get_class_constructor,
+    iterator::create_iterator,
+    register_class, register_class_parent, register_error_variant_class, register_fn_overload,
+    validate_class_instance, FnOverload, ___CALL_FROM_FACTORY,
   };
+  pub use crate::panic::{abort_on_panic, catch_unwind};
 
   use crate::sys;
 
@@ -0,0 +1,82 @@
+use std::any::Any;
+use std::cell::RefCell;
+#[cfg(any(feature = "executor", feature = "uv"))]
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(any(feature = "executor", feature = "uv"))]
+use std::pin::Pin;
+use std::process;
+use std::sync::Once;
+#[cfg(any(feature = "executor", feature = "uv"))]
+use std::task::{Context, Poll};
+
+use crate::{Error, Result};
+
+thread_local! {
+  static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// Record the location of the panic, so that it could be attached to the thrown `Error`.
+/// The previous hook is still called, the panic message will be printed to stderr as usual.
+fn install_panic_hook() {
+  INSTALL_PANIC_HOOK.call_once(|| {
+    let prev_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+      if let Some(location) = info.location() {
+        PANIC_LOCATION.with(|l| l.borrow_mut().replace(location.to_string()));
+      }
+      prev_hook(info);
+    }));
+  });
+}
+
+fn panic_to_error(payload: Box<dyn Any + Send>) -> Error {
+  let message = if let Some(s) = payload.downcast_ref::<String>() {
+    s.as_str()
+  } else if let Some(s) = payload.downcast_ref::<&str>() {
+    s
+  } else {
+    "<no error message>"
+  };
+  match PANIC_LOCATION.with(|l| l.borrow_mut().take()) {
+    Some(location) => Error::from_reason(format!(
+      "panic from Rust code: {}, at {}",
+      message, location
+    )),
+    None => Error::from_reason(format!("panic from Rust code: {}", message)),
+  }
+}
+
+/// Run `f` and convert the panic into `Error` instead of unwinding across the FFI boundary.
+///
+/// Aborts the process on panic like [`abort_on_panic`] if the `abort-on-panic` feature is enabled.
+#[doc(hidden)]
+pub fn catch_unwind<T, F: FnOnce() -> Result<T>>(f: F) -> Result<T> {
+  if cfg!(feature = "abort-on-panic") {
+    return abort_on_panic(f);
+  }
+  install_panic_hook();
+  panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(panic_to_error(payload)))
+}
+
+/// Run `f` and abort the process if it panicked, the panic can't unwind across the FFI boundary.
+#[doc(hidden)]
+pub fn abort_on_panic<T, F: FnOnce() -> T>(f: F) -> T {
+  panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| process::abort())
+}
+
+/// `Future` version of [`catch_unwind`], used to settle the `Promise` if the `async fn` panicked.
+#[cfg(any(feature = "executor", feature = "uv"))]
+pub(crate) struct CatchUnwind<F>(pub(crate) Pin<Box<F>>);
+
+#[cfg(any(feature = "executor", feature = "uv"))]
+impl<T, F: Future<Output = Result<T>>> Future for CatchUnwind<F> {
+  type Output = Result<T>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let fut = self.0.as_mut();
+    catch_unwind(|| Ok(fut.poll(cx))).unwrap_or_else(|e| Poll::Ready(Err(e)))
+  }
+}
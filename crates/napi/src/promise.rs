@@ -4,7 +4,11 @@ use std::marker::PhantomData;
 use std::os::raw::c_void;
 use std::ptr;
 
-use crate::{check_status, sys, JsError, Result};
+use crate::{
+  check_status,
+  panic::{catch_unwind, CatchUnwind},
+  sys, JsError, Result,
+};
 
 pub struct FuturePromise<Data, Resolver: FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>> {
   deferred: sys::napi_deferred,
@@ -74,7 +78,7 @@ pub(crate) async fn resolve_from_future<Data: Send, Fut: Future<Output = Result<
   tsfn_value: TSFNValue,
   fut: Fut,
 ) {
-  let val = fut.await;
+  let val = CatchUnwind(Box::pin(fut)).await;
   check_status!(unsafe {
     sys::napi_call_threadsafe_function(
       tsfn_value.0,
@@ -102,35 +106,15 @@ unsafe extern "C" fn call_js_cb<
   let value = unsafe { Box::from_raw(data as *mut Result<Data>) };
   let resolver = future_promise.resolver;
   let deferred = future_promise.deferred;
-  let js_value_to_resolve = value.and_then(move |v| (resolver)(env, v));
+  let js_value_to_resolve = value.and_then(move |v| catch_unwind(move || (resolver)(env, v)));
   match js_value_to_resolve {
     Ok(v) => {
       let status = unsafe { sys::napi_resolve_deferred(env, deferred, v) };
       debug_assert!(status == sys::Status::napi_ok, "Resolve promise failed");
     }
     Err(e) => {
-      let status = unsafe {
-        sys::napi_reject_deferred(
-          env,
-          deferred,
-          if e.maybe_raw.is_null() {
-            JsError::from(e).into_value(env)
-          } else {
-            let mut err = ptr::null_mut();
-            let get_err_status = sys::napi_get_reference_value(env, e.maybe_raw, &mut err);
-            debug_assert!(
-              get_err_status == sys::Status::napi_ok,
-              "Get Error from Reference failed"
-            );
-            let delete_reference_status = sys::napi_delete_reference(env, e.maybe_raw);
-            debug_assert!(
-              delete_reference_status == sys::Status::napi_ok,
-              "Delete Error Reference failed"
-            );
-            err
-          },
-        )
-      };
+      let status =
+        unsafe { sys::napi_reject_deferred(env, deferred, JsError::from(e).into_value(env)) };
       debug_assert!(status == sys::Status::napi_ok, "Reject promise failed");
     }
   };
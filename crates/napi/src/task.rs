@@ -1,3 +1,5 @@
+#[cfg(feature = "napi4")]
+use crate::bindgen_runtime::TaskContext;
 use crate::{
   bindgen_runtime::{ToNapiValue, TypeName},
   Env, Error, Result,
@@ -23,3 +25,28 @@ pub trait Task: Send + Sized {
     Ok(())
   }
 }
+
+/// A [`Task`] reporting its progress from `compute`, returned to JavaScript through `AsyncProgressTask`.
+#[cfg(feature = "napi4")]
+pub trait ProgressTask: Send + Sized {
+  type Output: Send + Sized + 'static;
+  type JsValue: ToNapiValue + TypeName;
+  /// Delivered to the `onProgress` callback on the JavaScript thread
+  type Progress: ToNapiValue + Send + 'static;
+
+  /// Compute logic in libuv thread, report the progress through `ctx`
+  fn compute(&mut self, ctx: &TaskContext<Self::Progress>) -> Result<Self::Output>;
+
+  /// Into this method if `compute` return `Ok`
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue>;
+
+  /// Into this method if `compute` return `Err`
+  fn reject(&mut self, _env: Env, err: Error) -> Result<Self::JsValue> {
+    Err(err)
+  }
+
+  // after resolve or reject
+  fn finally(&mut self, _env: Env) -> Result<()> {
+    Ok(())
+  }
+}
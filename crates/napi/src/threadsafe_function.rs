@@ -9,7 +9,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use crate::bindgen_runtime::ToNapiValue;
-use crate::{check_status, sys, Env, Error, JsError, Result, Status};
+use crate::{check_status, panic::catch_unwind, sys, Env, Error, JsError, Result, Status};
 
 /// ThreadSafeFunction Context object
 /// the `value` is the value passed to `call` method
@@ -368,9 +368,11 @@ unsafe extern "C" fn call_js_cb<T: 'static, V: ToNapiValue, R, ES>(
   unsafe { sys::napi_get_undefined(raw_env, &mut recv) };
 
   let ret = val.and_then(|v| {
-    (ctx)(ThreadSafeCallContext {
-      env: unsafe { Env::from_raw(raw_env) },
-      value: v,
+    catch_unwind(|| {
+      (ctx)(ThreadSafeCallContext {
+        env: unsafe { Env::from_raw(raw_env) },
+        value: v,
+      })
     })
   });
 
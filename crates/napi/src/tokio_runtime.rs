@@ -1,44 +1,113 @@
 use std::ffi::c_void;
-use std::future::Future;
-use std::ptr;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use lazy_static::lazy_static;
 use tokio::{
-  runtime::Handle,
+  runtime::{Builder, Handle, Runtime},
   sync::mpsc::{self, error::TrySendError},
 };
 
-use crate::{check_status, promise, sys, Result};
+pub use crate::executor::{execute_future as execute_tokio_future, spawn};
+use crate::{
+  executor::{Executor, SpawnedFuture},
+  sys, Error, Result, Status,
+};
+
+enum CustomRuntime {
+  Runtime(Runtime),
+  /// Owned by the embedding application, it's never shut down by napi-rs
+  Handle(Handle),
+}
 
 lazy_static! {
-  pub(crate) static ref RT: (Handle, mpsc::Sender<()>) = {
-    let runtime = tokio::runtime::Runtime::new();
-    let (sender, mut receiver) = mpsc::channel::<()>(1);
-    runtime
-      .map(|rt| {
-        let h = rt.handle();
-        let handle = h.clone();
-        handle.spawn(async move {
-          if receiver.recv().await.is_some() {
-            rt.shutdown_background();
-          }
-        });
-
-        (handle, sender)
-      })
-      .expect("Create tokio runtime failed")
+  static ref CUSTOM_RT: Mutex<Option<CustomRuntime>> = Mutex::new(None);
+}
+
+static RT_STARTED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+  pub(crate) static ref RT: (Handle, Option<mpsc::Sender<()>>) = {
+    let mut custom_rt = CUSTOM_RT.lock().expect("Lock custom tokio runtime failed");
+    RT_STARTED.store(true, Ordering::SeqCst);
+    match custom_rt.take() {
+      Some(CustomRuntime::Handle(handle)) => (handle, None),
+      Some(CustomRuntime::Runtime(rt)) => start_runtime(rt),
+      None => start_runtime(Runtime::new().expect("Create tokio runtime failed")),
+    }
   };
 }
 
+/// Drive the runtime in a dedicated thread, so the `current_thread` runtime works as well.
+/// The runtime is shut down once all the `Env`s using it are cleaned up.
+fn start_runtime(rt: Runtime) -> (Handle, Option<mpsc::Sender<()>>) {
+  let handle = rt.handle().clone();
+  let (sender, mut receiver) = mpsc::channel::<()>(1);
+  std::thread::Builder::new()
+    .name("napi-rs-tokio-runtime".to_owned())
+    .spawn(move || {
+      rt.block_on(receiver.recv());
+      rt.shutdown_background();
+    })
+    .expect("Spawn tokio runtime thread failed");
+  (handle, Some(sender))
+}
+
+fn set_custom_runtime(custom_rt: CustomRuntime) -> Result<()> {
+  let mut current = CUSTOM_RT.lock().map_err(|_| {
+    Error::new(
+      Status::GenericFailure,
+      "Lock custom tokio runtime failed".to_owned(),
+    )
+  })?;
+  if RT_STARTED.load(Ordering::SeqCst) {
+    return Err(Error::new(
+      Status::GenericFailure,
+      "The tokio runtime is already started, it must be configured in the `#[napi::module_init]` function"
+        .to_owned(),
+    ));
+  }
+  *current = Some(custom_rt);
+  Ok(())
+}
+
+/// Build the tokio runtime used by `async fn`, `Promise<T>` and `Env::execute_tokio_future` from the `Builder`,
+/// for example to change the worker threads, the thread names, the stack size or the enabled drivers.
+///
+/// Must be called before the runtime is started, typically in the `#[napi::module_init]` function:
+///
+/// ```rust,ignore
+/// #[napi::module_init]
+/// fn init() {
+///   let mut builder = napi::tokio::runtime::Builder::new_multi_thread();
+///   builder.worker_threads(2).thread_name("my-addon-worker").enable_all();
+///   napi::tokio_runtime::configure(builder).expect("Configure tokio runtime failed");
+/// }
+/// ```
+pub fn configure(mut builder: Builder) -> Result<()> {
+  set_custom_runtime(CustomRuntime::Runtime(builder.build()?))
+}
+
+/// Use the already built `Runtime` instead of the default multi-thread runtime, see [`configure`].
+pub fn create_custom_tokio_runtime(rt: Runtime) -> Result<()> {
+  set_custom_runtime(CustomRuntime::Runtime(rt))
+}
+
+/// Spawn the futures onto a runtime owned by the embedding application, see [`configure`].
+///
+/// The runtime behind the `Handle` must outlive the addon, it's not shut down when the `Env` is cleaned up.
+pub fn use_tokio_handle(handle: Handle) -> Result<()> {
+  set_custom_runtime(CustomRuntime::Handle(handle))
+}
+
 pub(crate) static TOKIO_RT_REF_COUNT: AtomicUsize = AtomicUsize::new(0);
 
 #[doc(hidden)]
 #[inline(never)]
 pub unsafe extern "C" fn shutdown_tokio_rt(arg: *mut c_void) {
-  if TOKIO_RT_REF_COUNT.fetch_sub(1, Ordering::SeqCst) == 0 {
-    let sender = &RT.1;
-    if let Err(e) = sender.clone().try_send(()) {
+  // the runtime is never started if another executor is used
+  if TOKIO_RT_REF_COUNT.fetch_sub(1, Ordering::SeqCst) == 0 && RT_STARTED.load(Ordering::SeqCst) {
+    if let Some(Err(e)) = RT.1.as_ref().map(|sender| sender.try_send(())) {
       match e {
         TrySendError::Closed(_) => {}
         TrySendError::Full(_) => {
@@ -54,31 +123,11 @@ pub unsafe extern "C" fn shutdown_tokio_rt(arg: *mut c_void) {
   }
 }
 
-pub fn spawn<F>(fut: F)
-where
-  F: 'static + Send + Future<Output = ()>,
-{
-  RT.0.spawn(fut);
-}
+/// The default [`Executor`], spawns the futures onto the tokio runtime, which could be configured with [`configure`].
+pub struct TokioExecutor;
 
-#[allow(clippy::not_unsafe_ptr_arg_deref)]
-pub fn execute_tokio_future<
-  Data: 'static + Send,
-  Fut: 'static + Send + Future<Output = Result<Data>>,
-  Resolver: 'static + Send + Sync + FnOnce(sys::napi_env, Data) -> Result<sys::napi_value>,
->(
-  env: sys::napi_env,
-  fut: Fut,
-  resolver: Resolver,
-) -> Result<sys::napi_value> {
-  let mut promise = ptr::null_mut();
-  let mut deferred = ptr::null_mut();
-
-  check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) })?;
-
-  let future_promise = promise::FuturePromise::new(env, deferred, resolver)?;
-  let future_to_resolve = promise::resolve_from_future(future_promise.start()?, fut);
-  spawn(future_to_resolve);
-
-  Ok(promise)
+impl Executor for TokioExecutor {
+  fn spawn(&self, fut: SpawnedFuture) {
+    RT.0.spawn(fut);
+  }
 }
@@ -0,0 +1,379 @@
+//! Drive `!Send` futures on the JavaScript thread through the libuv event loop of Node.js.
+//!
+//! The futures spawned by [`Env::spawn_local`] are polled in a `uv_async_t` callback, so they could hold
+//! `Env`, `Ref` and the other `!Send` values across `.await`, and settle the returned `Promise` directly.
+//! Every poll runs in its own handle scope, the JavaScript values created in a poll must be kept in a `Ref`
+//! to be used after `.await`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+use crate::{
+  bindgen_runtime::ToNapiValue,
+  check_status,
+  panic::{catch_unwind, CatchUnwind},
+  sys, Env, Error, JsError, Result, Status,
+};
+
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+  static LOCAL_EXECUTORS: RefCell<HashMap<usize, Rc<LocalExecutor>>> = RefCell::new(HashMap::new());
+}
+
+struct AsyncHandle(*mut sys::uv_async_t);
+
+unsafe impl Send for AsyncHandle {}
+
+#[derive(Default)]
+struct WakeQueueInner {
+  ready: Vec<usize>,
+  /// `None` once the executor is closed
+  async_handle: Option<AsyncHandle>,
+}
+
+/// Shared with the `Waker`s, which may be woken from any thread
+#[derive(Default)]
+struct WakeQueue(Mutex<WakeQueueInner>);
+
+impl WakeQueue {
+  fn schedule(&self, id: usize) {
+    let mut inner = self.0.lock().expect("Lock wake queue failed");
+    if let Some(async_handle) = &inner.async_handle {
+      // `uv_async_send` is the only thread-safe libuv function
+      unsafe { sys::uv_async_send(async_handle.0) };
+      inner.ready.push(id);
+    }
+  }
+
+  fn take_ready(&self) -> Vec<usize> {
+    std::mem::take(&mut self.0.lock().expect("Lock wake queue failed").ready)
+  }
+
+  fn close(&self) {
+    self.0.lock().expect("Lock wake queue failed").async_handle = None;
+  }
+}
+
+struct TaskWaker {
+  id: usize,
+  queue: Arc<WakeQueue>,
+}
+
+impl Wake for TaskWaker {
+  fn wake(self: Arc<Self>) {
+    self.queue.schedule(self.id);
+  }
+
+  fn wake_by_ref(self: &Arc<Self>) {
+    self.queue.schedule(self.id);
+  }
+}
+
+/// One executor per `Env`, it only keeps the event loop alive while there are pending tasks
+struct LocalExecutor {
+  async_handle: *mut sys::uv_async_t,
+  async_context: sys::napi_async_context,
+  queue: Arc<WakeQueue>,
+  tasks: RefCell<HashMap<usize, LocalTask>>,
+  next_id: Cell<usize>,
+}
+
+impl LocalExecutor {
+  fn get_or_create(env: sys::napi_env) -> Result<Rc<Self>> {
+    if let Some(executor) =
+      LOCAL_EXECUTORS.with(|executors| executors.borrow().get(&(env as usize)).cloned())
+    {
+      return Ok(executor);
+    }
+    let mut uv_loop = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_uv_event_loop(env, &mut uv_loop) },
+      "Get uv event loop failed"
+    )?;
+    let mut resource = ptr::null_mut();
+    check_status!(unsafe { sys::napi_create_object(env, &mut resource) })?;
+    let mut resource_name = ptr::null_mut();
+    let name = "napi_rs_local_executor";
+    check_status!(unsafe {
+      sys::napi_create_string_utf8(
+        env,
+        name.as_ptr() as *const _,
+        name.len(),
+        &mut resource_name,
+      )
+    })?;
+    let mut async_context = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_async_init(env, resource, resource_name, &mut async_context) },
+      "Init async context of the local executor failed"
+    )?;
+
+    let async_handle = unsafe { alloc_handle::<sys::uv_async_t>(sys::UvHandleType::UV_ASYNC) };
+    let status = unsafe { sys::uv_async_init(uv_loop, async_handle, Some(poll_local_tasks)) };
+    if status != 0 {
+      unsafe {
+        free_handle(async_handle as *mut _, sys::UvHandleType::UV_ASYNC);
+        sys::napi_async_destroy(env, async_context);
+      };
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("Init uv_async_t failed with {}", status),
+      ));
+    }
+    unsafe {
+      sys::uv_handle_set_data(async_handle as *mut _, env as *mut c_void);
+      sys::uv_unref(async_handle as *mut _);
+    };
+    check_status!(unsafe {
+      sys::napi_add_env_cleanup_hook(env, Some(close_local_executor), env as *mut c_void)
+    })?;
+
+    let queue = Arc::new(WakeQueue::default());
+    queue.0.lock().expect("Lock wake queue failed").async_handle = Some(AsyncHandle(async_handle));
+    let executor = Rc::new(LocalExecutor {
+      async_handle,
+      async_context,
+      queue,
+      tasks: RefCell::new(HashMap::new()),
+      next_id: Cell::new(0),
+    });
+    LOCAL_EXECUTORS.with(|executors| {
+      executors
+        .borrow_mut()
+        .insert(env as usize, executor.clone())
+    });
+    Ok(executor)
+  }
+
+  fn spawn(&self, task: LocalTask) {
+    let id = self.next_id.get();
+    self.next_id.set(id.wrapping_add(1));
+    let mut tasks = self.tasks.borrow_mut();
+    if tasks.is_empty() {
+      unsafe { sys::uv_ref(self.async_handle as *mut _) };
+    }
+    tasks.insert(id, task);
+    self.queue.schedule(id);
+  }
+
+  /// Poll the woken tasks once, the tasks woken while polling are polled in the next `uv_async_t` callback
+  fn poll_ready(&self) {
+    for id in self.queue.take_ready() {
+      // the task could spawn new tasks while it's polled
+      let task = self.tasks.borrow_mut().remove(&id);
+      if let Some(mut task) = task {
+        let waker = Waker::from(Arc::new(TaskWaker {
+          id,
+          queue: self.queue.clone(),
+        }));
+        let mut cx = Context::from_waker(&waker);
+        if task.as_mut().poll(&mut cx).is_pending() {
+          self.tasks.borrow_mut().insert(id, task);
+        }
+      }
+    }
+    if self.tasks.borrow().is_empty() {
+      unsafe { sys::uv_unref(self.async_handle as *mut _) };
+    }
+  }
+}
+
+unsafe extern "C" fn poll_local_tasks(handle: *mut sys::uv_async_t) {
+  let env = unsafe { sys::uv_handle_get_data(handle as *const _) } as sys::napi_env;
+  let executor =
+    match LOCAL_EXECUTORS.with(|executors| executors.borrow().get(&(env as usize)).cloned()) {
+      Some(executor) => executor,
+      None => return,
+    };
+  let mut handle_scope = ptr::null_mut();
+  let mut callback_scope = ptr::null_mut();
+  let mut resource = ptr::null_mut();
+  unsafe {
+    sys::napi_open_handle_scope(env, &mut handle_scope);
+    sys::napi_create_object(env, &mut resource);
+    // the microtasks, for example the reactions of the settled `Promise`s, run after the callback scope is closed
+    sys::napi_open_callback_scope(env, resource, executor.async_context, &mut callback_scope);
+  };
+  executor.poll_ready();
+  let mut is_exception_pending = false;
+  unsafe { sys::napi_is_exception_pending(env, &mut is_exception_pending) };
+  if is_exception_pending {
+    let mut exception = ptr::null_mut();
+    unsafe {
+      sys::napi_get_and_clear_last_exception(env, &mut exception);
+      sys::napi_fatal_exception(env, exception);
+    };
+  }
+  unsafe {
+    sys::napi_close_callback_scope(env, callback_scope);
+    sys::napi_close_handle_scope(env, handle_scope);
+  };
+}
+
+unsafe extern "C" fn close_local_executor(env: *mut c_void) {
+  let executor = LOCAL_EXECUTORS.with(|executors| executors.borrow_mut().remove(&(env as usize)));
+  if let Some(executor) = executor {
+    executor.queue.close();
+    unsafe {
+      sys::uv_close(executor.async_handle as *mut _, Some(close_async_handle));
+      sys::napi_async_destroy(env as sys::napi_env, executor.async_context);
+    };
+    // the pending tasks are dropped without being settled
+    let tasks = std::mem::take(&mut *executor.tasks.borrow_mut());
+    drop(tasks);
+  }
+}
+
+unsafe extern "C" fn close_async_handle(handle: *mut sys::uv_handle_t) {
+  unsafe { free_handle(handle, sys::UvHandleType::UV_ASYNC) };
+}
+
+/// The size of the libuv handles is only known at runtime
+unsafe fn alloc_handle<H>(handle_type: sys::uv_handle_type) -> *mut H {
+  let words = (unsafe { sys::uv_handle_size(handle_type) } + 7) / 8;
+  Box::into_raw(vec![0u64; words].into_boxed_slice()) as *mut H
+}
+
+unsafe fn free_handle(handle: *mut sys::uv_handle_t, handle_type: sys::uv_handle_type) {
+  let words = (unsafe { sys::uv_handle_size(handle_type) } + 7) / 8;
+  drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(handle as *mut u64, words)) });
+}
+
+pub(crate) fn spawn_local<T, F>(env: sys::napi_env, fut: F) -> Result<sys::napi_value>
+where
+  T: ToNapiValue,
+  F: 'static + Future<Output = Result<T>>,
+{
+  let executor = LocalExecutor::get_or_create(env)?;
+  let mut deferred = ptr::null_mut();
+  let mut promise = ptr::null_mut();
+  check_status!(unsafe { sys::napi_create_promise(env, &mut deferred, &mut promise) })?;
+  executor.spawn(Box::pin(async move {
+    let result = CatchUnwind(Box::pin(fut))
+      .await
+      .and_then(|value| catch_unwind(|| unsafe { T::to_napi_value(env, value) }));
+    let status = match result {
+      Ok(value) => unsafe { sys::napi_resolve_deferred(env, deferred, value) },
+      Err(err) => unsafe {
+        sys::napi_reject_deferred(env, deferred, JsError::from(err).into_value(env))
+      },
+    };
+    debug_assert!(status == sys::Status::napi_ok, "Settle promise failed");
+  }));
+  Ok(promise)
+}
+
+#[derive(Default)]
+struct TimerState {
+  fired: bool,
+  waker: Option<Waker>,
+}
+
+/// A `Future` completed after the `duration`, backed by a `uv_timer_t` on the JavaScript thread.
+///
+/// It's only meant to be awaited in the futures spawned by [`Env::spawn_local`], the timer is cancelled if it's dropped.
+pub struct Sleep {
+  env: sys::napi_env,
+  duration: Duration,
+  timer: Option<*mut sys::uv_timer_t>,
+  state: Rc<RefCell<TimerState>>,
+}
+
+/// Wait for the `duration` without blocking the JavaScript thread, see [`Sleep`].
+pub fn sleep(env: &Env, duration: Duration) -> Sleep {
+  Sleep {
+    env: env.0,
+    duration,
+    timer: None,
+    state: Rc::new(RefCell::new(TimerState::default())),
+  }
+}
+
+impl Sleep {
+  fn start(&mut self) -> Result<()> {
+    let mut uv_loop = ptr::null_mut();
+    check_status!(
+      unsafe { sys::napi_get_uv_event_loop(self.env, &mut uv_loop) },
+      "Get uv event loop failed"
+    )?;
+    let timer = unsafe { alloc_handle::<sys::uv_timer_t>(sys::UvHandleType::UV_TIMER) };
+    let status = unsafe { sys::uv_timer_init(uv_loop, timer) };
+    if status != 0 {
+      unsafe { free_handle(timer as *mut _, sys::UvHandleType::UV_TIMER) };
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("Init uv_timer_t failed with {}", status),
+      ));
+    }
+    // released in `close_timer`
+    let state = Rc::into_raw(self.state.clone());
+    unsafe { sys::uv_handle_set_data(timer as *mut _, state as *mut c_void) };
+    self.timer = Some(timer);
+    let timeout = self.duration.as_millis().min(u64::MAX as u128) as u64;
+    let status = unsafe { sys::uv_timer_start(timer, Some(on_timer), timeout, 0) };
+    if status != 0 {
+      return Err(Error::new(
+        Status::GenericFailure,
+        format!("Start uv_timer_t failed with {}", status),
+      ));
+    }
+    Ok(())
+  }
+}
+
+impl Future for Sleep {
+  type Output = Result<()>;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    if self.state.borrow().fired {
+      return Poll::Ready(Ok(()));
+    }
+    self.state.borrow_mut().waker = Some(cx.waker().clone());
+    if self.timer.is_none() {
+      if let Err(err) = self.start() {
+        return Poll::Ready(Err(err));
+      }
+    }
+    Poll::Pending
+  }
+}
+
+impl Drop for Sleep {
+  fn drop(&mut self) {
+    if let Some(timer) = self.timer.take() {
+      unsafe {
+        sys::uv_timer_stop(timer);
+        sys::uv_close(timer as *mut _, Some(close_timer));
+      };
+    }
+  }
+}
+
+unsafe extern "C" fn on_timer(timer: *mut sys::uv_timer_t) {
+  let state =
+    unsafe { &*(sys::uv_handle_get_data(timer as *const _) as *const RefCell<TimerState>) };
+  let waker = {
+    let mut state = state.borrow_mut();
+    state.fired = true;
+    state.waker.take()
+  };
+  if let Some(waker) = waker {
+    waker.wake();
+  }
+}
+
+unsafe extern "C" fn close_timer(timer: *mut sys::uv_handle_t) {
+  drop(unsafe {
+    Rc::from_raw(sys::uv_handle_get_data(timer as *const _) as *const RefCell<TimerState>)
+  });
+  unsafe { free_handle(timer, sys::UvHandleType::UV_TIMER) };
+}
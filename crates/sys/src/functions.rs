@@ -719,6 +719,39 @@ mod experimental {
   );
 }
 
+/// libuv functions exported by Node.js, used to drive the futures on the JavaScript thread
+#[cfg(feature = "uv")]
+mod uv {
+  use std::os::raw::{c_int, c_void};
+
+  use super::super::types::*;
+
+  generate!(
+    extern "C" {
+      fn uv_handle_size(type_: uv_handle_type) -> usize;
+      fn uv_handle_get_data(handle: *const uv_handle_t) -> *mut c_void;
+      fn uv_handle_set_data(handle: *mut uv_handle_t, data: *mut c_void);
+      fn uv_ref(handle: *mut uv_handle_t);
+      fn uv_unref(handle: *mut uv_handle_t);
+      fn uv_close(handle: *mut uv_handle_t, close_cb: uv_close_cb);
+      fn uv_async_init(
+        loop_: *mut uv_loop_s,
+        async_: *mut uv_async_t,
+        async_cb: uv_async_cb,
+      ) -> c_int;
+      fn uv_async_send(async_: *mut uv_async_t) -> c_int;
+      fn uv_timer_init(loop_: *mut uv_loop_s, handle: *mut uv_timer_t) -> c_int;
+      fn uv_timer_start(
+        handle: *mut uv_timer_t,
+        cb: uv_timer_cb,
+        timeout: u64,
+        repeat: u64,
+      ) -> c_int;
+      fn uv_timer_stop(handle: *mut uv_timer_t) -> c_int;
+    }
+  );
+}
+
 #[cfg(feature = "experimental")]
 pub use experimental::*;
 pub use napi1::*;
@@ -736,6 +769,8 @@ pub use napi6::*;
 pub use napi7::*;
 #[cfg(feature = "napi8")]
 pub use napi8::*;
+#[cfg(feature = "uv")]
+pub use uv::*;
 
 #[cfg(windows)]
 pub(super) unsafe fn load() -> Result<(), libloading::Error> {
@@ -764,5 +799,7 @@ pub(super) unsafe fn load() -> Result<(), libloading::Error> {
   napi8::load(&host)?;
   #[cfg(feature = "experimental")]
   experimental::load(&host)?;
+  #[cfg(feature = "uv")]
+  uv::load(&host)?;
   Ok(())
 }
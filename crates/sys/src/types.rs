@@ -57,6 +57,38 @@ pub struct uv_loop_s {
 }
 pub type napi_deferred = *mut napi_deferred__;
 
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_handle_s {
+  _unused: [u8; 0],
+}
+pub type uv_handle_t = uv_handle_s;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_async_s {
+  _unused: [u8; 0],
+}
+pub type uv_async_t = uv_async_s;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct uv_timer_s {
+  _unused: [u8; 0],
+}
+pub type uv_timer_t = uv_timer_s;
+
+pub type uv_handle_type = i32;
+
+pub mod UvHandleType {
+  use super::uv_handle_type;
+
+  pub const UV_ASYNC: uv_handle_type = 1;
+  pub const UV_TIMER: uv_handle_type = 13;
+}
+
+pub type uv_close_cb = Option<unsafe extern "C" fn(handle: *mut uv_handle_t)>;
+pub type uv_async_cb = Option<unsafe extern "C" fn(handle: *mut uv_async_t)>;
+pub type uv_timer_cb = Option<unsafe extern "C" fn(handle: *mut uv_timer_t)>;
+
 pub type napi_property_attributes = i32;
 
 pub mod PropertyAttributes {
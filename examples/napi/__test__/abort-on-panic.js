@@ -0,0 +1,3 @@
+const { panicAndAbort } = require('../index.node')
+
+panicAndAbort()
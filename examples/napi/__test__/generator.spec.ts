@@ -1,6 +1,14 @@
 import test from 'ava'
 
-import { Fib, Fib2, Fib3 } from '../index'
+import {
+  Fib,
+  Fib2,
+  Fib3,
+  PageCursor,
+  fibNumbers,
+  parseNumbers,
+  splitWords,
+} from '../index'
 
 for (const [index, factory] of [
   () => new Fib(),
@@ -55,3 +63,74 @@ for (const [index, factory] of [
     })
   })
 }
+
+test('should be able to iterate an async generator', async (t) => {
+  const pages = []
+  for await (const page of new PageCursor(3, 2)) {
+    pages.push(page)
+  }
+  t.deepEqual(pages, [
+    [0, 1],
+    [2, 3],
+    [4, 5],
+  ])
+})
+
+test('should be able to stop an async generator', async (t) => {
+  const iterator = new PageCursor(3, 1)[Symbol.asyncIterator]()
+  t.deepEqual(await iterator.next(), {
+    done: false,
+    value: [0],
+  })
+  t.deepEqual(await iterator.return(), {
+    done: true,
+  })
+  t.deepEqual(await iterator.next(), {
+    done: true,
+  })
+})
+
+test('should queue the concurrent calls on async generator', async (t) => {
+  const iterator = new PageCursor(2, 1)[Symbol.asyncIterator]()
+  t.deepEqual(
+    await Promise.all([
+      iterator.next(),
+      iterator.next(),
+      iterator.next(),
+      iterator.return(),
+      iterator.next(),
+    ]),
+    [
+      { done: false, value: [0] },
+      { done: false, value: [1] },
+      { done: true },
+      { done: true },
+      { done: true },
+    ],
+  )
+})
+
+test('should be able to throw to async generator', async (t) => {
+  const iterator = new PageCursor(3, 1)[Symbol.asyncIterator]()
+  await t.throwsAsync(() => iterator.throw(new Error('boom')), {
+    message: 'boom',
+  })
+  t.deepEqual(await iterator.next(), {
+    done: true,
+  })
+})
+
+test('should convert returned iterators into iterables', (t) => {
+  t.deepEqual([...fibNumbers(7)], [0, 1, 1, 2, 3, 5, 8])
+  t.deepEqual([...splitWords(' hello  napi rs ')], ['hello', 'napi', 'rs'])
+  t.deepEqual([...parseNumbers('1, 2,3')], [1, 2, 3])
+  t.throws(() => parseNumbers('1,x'), {
+    code: 'InvalidArg',
+    message: '`x` is not a number',
+  })
+
+  const iterator = fibNumbers(3)[Symbol.iterator]()
+  t.deepEqual(iterator.next(), { value: 0, done: false })
+  t.deepEqual(iterator.return?.(), { done: true })
+  t.deepEqual(iterator.next(), { done: true })
+})
@@ -1,5 +1,6 @@
 import { exec } from 'child_process'
 import { join } from 'path'
+import { Readable } from 'stream'
 
 import test from 'ava'
 import { spy } from 'sinon'
@@ -16,20 +17,53 @@ import {
   getNums,
   getWords,
   sumNums,
+  sumIterable,
+  takeFromIterable,
   getMapping,
   sumMapping,
   getCwd,
   Animal,
+  Cat,
+  getAnimalName,
   Kind,
   ClassWithFactory,
   CustomNumEnum,
   Context,
   enumToI32,
+  accessModeToFlags,
+  oppositeLineEnding,
+  runCommand,
+  scaleShape,
   listObjKeys,
   createObj,
   mapOption,
+  repeatWithDefault,
+  padWithDefault,
+  parseNumber,
+  plusOne,
+  describePet,
+  lineBreak,
+  sumRest,
+  getThisName,
+  increaseThisCount,
+  formatMessage,
+  scaleWithDefault,
+  roundWithDefault,
   readFile,
   throwError,
+  throwTypeErrorWithCode,
+  throwErrorWithCause,
+  loadConfig,
+  ConfigError,
+  CacheError,
+  readCache,
+  parsePort,
+  parsePortAsync,
+  parsePortInTask,
+  Port,
+  panicInFn,
+  panicInAsyncFn,
+  panicInTask,
   readPackageJson,
   getPackageJsonName,
   getBuffer,
@@ -41,7 +75,27 @@ import {
   either4,
   withoutAbortController,
   withAbortController,
+  waitForAbort,
+  getAbortedFutures,
+  yieldWithSignal,
+  getObservedAborts,
+  throwIfAborted,
+  listenToAbort,
+  getAbortCallbacks,
+  pollUntilAborted,
+  countWithProgress,
   asyncMultiTwo,
+  AsyncCounter,
+  countStream,
+  getTokioThreadName,
+  getSpawnedFutures,
+  callAfter,
+  sumAsyncIterable,
+  concatAsyncIterable,
+  takeFromAsyncIterable,
+  naturalNumbers,
+  pendingStream,
+  getDroppedStreams,
   bigintAdd,
   createBigInt,
   createBigIntI64,
@@ -49,6 +103,7 @@ import {
   callThreadsafeFunction,
   threadsafeFunctionThrowError,
   asyncPlus100,
+  asyncPlus100Or,
   getGlobal,
   getUndefined,
   getNull,
@@ -93,6 +148,8 @@ import {
   CssStyleSheet,
   asyncReduceBuffer,
   callbackReturnPromise,
+  callAndRethrow,
+  catchJsException,
   returnEitherClass,
   eitherFromOption,
   overrideIndividualArgOnFunction,
@@ -138,6 +195,37 @@ test('array', (t) => {
   t.is(sumNums([1, 2, 3, 4, 5]), 15)
 })
 
+test('iterable', (t) => {
+  t.is(sumIterable(new Set([1, 2, 3])), 6)
+  t.is(sumIterable([4, 5]), 9)
+  t.is(sumIterable(new Map([[1, 'a'], [2, 'b']]).keys()), 3)
+  t.deepEqual(takeFromIterable('abc', 2), ['a', 'b'])
+
+  function* failing() {
+    yield 1
+    throw new Error('boom')
+  }
+  t.throws(() => sumIterable(failing()), { message: 'boom' })
+  t.throws(() => sumIterable(1 as any), {
+    code: 'InvalidArg',
+    message: 'Value is not iterable',
+  })
+
+  let finished = false
+  function* infinite() {
+    try {
+      let i = 0
+      while (true) {
+        yield `${i++}`
+      }
+    } finally {
+      finished = true
+    }
+  }
+  t.deepEqual(takeFromIterable(infinite(), 3), ['0', '1', '2'])
+  t.true(finished)
+})
+
 test('map', (t) => {
   t.deepEqual(getMapping(), { a: 101, b: 102 })
   t.is(sumMapping({ a: 101, b: 102 }), 203)
@@ -148,6 +236,71 @@ test('enum', (t) => {
   t.is(enumToI32(CustomNumEnum.Eight), 8)
 })
 
+test('string enum', (t) => {
+  t.is(accessModeToFlags('read'), 0)
+  t.is(accessModeToFlags('write'), 1)
+  t.is(accessModeToFlags('read+write'), 2)
+  t.is(oppositeLineEnding('lf'), 'cr-lf')
+  t.is(oppositeLineEnding('cr-lf'), 'lf')
+  t.throws(
+    // @ts-expect-error
+    () => accessModeToFlags('execute'),
+    {
+      code: 'InvalidArg',
+      message:
+        'value `"execute"` does not match any variant of enum `AccessMode`, expect "read" | "write" | "read+write"',
+    },
+  )
+})
+
+test('tagged enum', (t) => {
+  t.deepEqual(runCommand({ type: 'Move', x: 1, y: 2 }), {
+    type: 'Move',
+    x: 2,
+    y: 4,
+  })
+  t.deepEqual(runCommand({ type: 'Say', text: 'hi' }), {
+    type: 'Say',
+    text: 'HI',
+  })
+  t.deepEqual(runCommand({ type: 'Say', text: 'hi', to: 'napi' }), {
+    type: 'Say',
+    text: 'HI',
+    to: 'napi',
+  })
+  t.deepEqual(runCommand({ type: 'quit' }), { type: 'quit' })
+  t.deepEqual(scaleShape({ kind: 'circle', value: 1.5 }, 2), {
+    kind: 'circle',
+    value: 3,
+  })
+  t.deepEqual(scaleShape({ kind: 'rectangle', value: [1, 2] }, 2), {
+    kind: 'rectangle',
+    value: [2, 4],
+  })
+  t.deepEqual(
+    scaleShape({ kind: 'polygon', value: { points: [1, 2], closed: true } }, 2),
+    { kind: 'polygon', value: { points: [2, 4], closed: true } },
+  )
+  t.deepEqual(scaleShape({ kind: 'empty' }, 2), { kind: 'empty' })
+  t.throws(
+    // @ts-expect-error
+    () => runCommand({ type: 'Jump' }),
+    {
+      code: 'InvalidArg',
+      message:
+        'tag `"Jump"` does not match any variant of enum `Command`, expect "Move" | "Say" | "quit"',
+    },
+  )
+  t.throws(
+    // @ts-expect-error
+    () => runCommand({ type: 'Move', x: 1 }),
+    {
+      code: 'InvalidArg',
+      message: 'Missing field `y` of variant `Move`',
+    },
+  )
+})
+
 test('class', (t) => {
   const dog = new Animal(Kind.Dog, '旺财')
 
@@ -171,6 +324,23 @@ test('class', (t) => {
   t.is(assets.get(1)?.filePath, 1)
 })
 
+test('class inheritance', (t) => {
+  const cat = new Cat('Tom')
+
+  t.true(cat instanceof Cat)
+  t.true(cat instanceof Animal)
+  t.is(Object.getPrototypeOf(Cat), Animal)
+  t.is(cat.whoami(), 'Cat: Tom')
+  t.is(cat.type, Kind.Cat)
+  cat.name = 'Jerry'
+  t.is(cat.name, 'Jerry')
+  t.is(cat.lives, 9)
+  t.is(cat.loseLife(), 8)
+  t.is(cat.introduce(), 'Cat: Jerry with 8 lives')
+  t.is(getAnimalName(cat), 'Jerry')
+  t.is(Cat.getDogKind(), Kind.Dog)
+})
+
 test('class factory', (t) => {
   const duck = ClassWithFactory.withName('Default')
   t.is(duck.name, 'Default')
@@ -240,6 +410,33 @@ test('callback', (t) => {
   })
 })
 
+test('exception thrown from callback', (t) => {
+  const fxError = new Error('What is Happy Planet')
+  const err = t.throws(() =>
+    callAndRethrow(() => {
+      throw fxError
+    }),
+  )
+  t.is(err, fxError)
+  try {
+    callAndRethrow(() => {
+      throw 'not an Error'
+    })
+    t.fail('exception should be rethrown')
+  } catch (e) {
+    t.is(e, 'not an Error')
+  }
+  const caught = catchJsException(() => {
+    throw new TypeError('Unexpected token')
+  })
+  t.is(caught?.message, 'Unexpected token')
+  t.true(caught?.stack?.startsWith('TypeError: Unexpected token\n'))
+  t.is(
+    catchJsException(() => {}),
+    null,
+  )
+})
+
 test('return function', (t) => {
   return new Promise<void>((resolve) => {
     returnJsFunction()((err: Error | undefined, content: string) => {
@@ -319,10 +516,227 @@ test('Option', (t) => {
   t.is(mapOption(3), 4)
 })
 
+test('default arguments', (t) => {
+  t.is(repeatWithDefault(), 'napinapi')
+  t.is(repeatWithDefault('a'), 'aa')
+  t.is(repeatWithDefault(undefined, 3), 'napinapinapi')
+  t.is(repeatWithDefault('b', 3), 'bbb')
+  t.is(scaleWithDefault(undefined, 2), 3)
+  t.is(scaleWithDefault(3, 2), 6)
+  // `null` is replaced by the default value as well
+  t.is(repeatWithDefault(null, null), 'napinapi')
+  t.is(scaleWithDefault(null, 2), 3)
+  t.is(padWithDefault('a'), '   a')
+  t.is(padWithDefault('a', null), '   a')
+  t.is(padWithDefault('a', 2), ' a')
+  t.is(roundWithDefault(2.4), 2)
+  t.is(roundWithDefault(2.4, 0.5), 2.5)
+})
+
+test('rest arguments', (t) => {
+  t.is(sumRest(), 0)
+  t.is(sumRest(1, 2, 3), 6)
+  t.is(formatMessage('plain'), 'plain')
+  t.is(formatMessage('{} is {}', 'napi', 2), 'napi is 2')
+  t.throws(() => formatMessage('{} {}', 'napi'), {
+    code: 'InvalidArg',
+    message: 'Not enough arguments for the template',
+  })
+})
+
+test('this parameter', (t) => {
+  t.is(getThisName.call({ name: 'napi' }), 'napi')
+  t.is(getThisName.call({}), null)
+  const counter = { count: 1, increase: increaseThisCount }
+  t.is(counter.increase(2), 3)
+  t.is(counter.count, 3)
+})
+
+test('function overloads', (t) => {
+  t.is(parseNumber('42'), 42)
+  t.is(parseNumber('ff', 16), 255)
+  t.is(parseNumber(3.7), 3)
+  t.is(parseNumber(Buffer.from('12')), 12)
+  t.throws(
+    // @ts-expect-error
+    () => parseNumber(true),
+    {
+      code: 'InvalidArg',
+      message:
+        'Arguments do not match any overload of the function, expect one of `parseNumber(input: string, radix?: number | undefined | null)`, `parseNumber(input: number)`, `parseNumber(input: Buffer)`',
+    },
+  )
+})
+
+test('function overloads with Promise', async (t) => {
+  t.is(plusOne(1), 2)
+  t.is(await plusOne(Promise.resolve(41)), 42)
+})
+
+test('function overloads with class instances and objects', (t) => {
+  t.is(describePet(new Dog('Doge')), 'Dog: Doge')
+  t.is(describePet(new Bird('parrot')), 'Bird: parrot')
+  t.is(describePet({ name: 'Tom' }), 'Pet: Tom')
+  t.throws(
+    // @ts-expect-error
+    () => describePet({}),
+    {
+      code: 'InvalidArg',
+      message:
+        'Arguments do not match any overload of the function, expect one of `describePet(dog: Dog)`, `describePet(bird: Bird)`, `describePet(pet: Pet)`',
+    },
+  )
+})
+
+test('function overloads with string enum', (t) => {
+  t.is(lineBreak('lf'), '\n')
+  t.is(lineBreak('cr-lf'), '\r\n')
+  t.is(lineBreak('<br>'), '<br>')
+})
+
 test('Result', (t) => {
   t.throws(() => throwError(), void 0, 'Manual Error')
 })
 
+test('Result with native stack', (t) => {
+  const err = t.throws(() => throwError()) as Error & { nativeStack: string }
+  t.true(err.nativeStack.includes('throw_error'))
+  t.false(Object.keys(err).includes('nativeStack'))
+})
+
+test('Result with error kind and code', (t) => {
+  t.throws(() => throwTypeErrorWithCode(), {
+    instanceOf: TypeError,
+    message: 'Expect a positive number',
+    code: 'ERR_NEGATIVE_NUMBER',
+  })
+})
+
+test('Result with error cause', (t) => {
+  const err = t.throws(() => throwErrorWithCause(), {
+    message: 'Failed to read config',
+    code: 'GenericFailure',
+  })
+  const cause = err.cause as RangeError & { code: string }
+  t.true(cause instanceof RangeError)
+  t.is(cause.message, 'Config file is too large')
+  t.is(cause.code, 'ERR_FILE_TOO_LARGE')
+})
+
+test('Result with #[napi(error)] enum', (t) => {
+  t.is(loadConfig('name: napi'), 'name: napi')
+  const notFound = t.throws(() => loadConfig(''), {
+    instanceOf: ConfigError.NotFound,
+    name: 'NotFound',
+    message: 'config file `napi.config.json` is not found',
+  })
+  t.true(notFound instanceof ConfigError)
+  t.true(notFound instanceof Error)
+  t.is(notFound.path, 'napi.config.json')
+  const invalidValue = t.throws(() => loadConfig('name'), {
+    instanceOf: ConfigError.InvalidValue,
+    message: 'invalid value at line 1: expect `key: value`',
+  })
+  t.is(invalidValue.line, 1)
+  t.is(invalidValue.reason, 'expect `key: value`')
+  const unsupported = t.throws(() => loadConfig('version: 2'), {
+    instanceOf: ConfigError.UnsupportedVersion,
+  })
+  t.is(unsupported.field0, 2)
+  t.throws(() => loadConfig('locked'), {
+    instanceOf: ConfigError.ConfigLockedError,
+    name: 'ConfigLockedError',
+    message: 'config file is locked',
+  })
+})
+
+test('Result with custom error type', async (t) => {
+  t.is(parsePort('80'), 80)
+  t.throws(() => parsePort('port'), {
+    instanceOf: RangeError,
+    message: '`port` is not a valid port',
+    code: 'InvalidArg',
+  })
+  t.is(await parsePortAsync('443'), 443)
+  await t.throwsAsync(() => parsePortAsync('-1'), {
+    instanceOf: RangeError,
+    message: '`-1` is not a valid port',
+  })
+  t.is(await parsePortInTask('8080'), 8080)
+  t.throws(() => parsePortInTask('65536'), {
+    instanceOf: RangeError,
+    message: '`65536` is not a valid port',
+  })
+  const port = Port.parse('65535')
+  t.is(port.value, 65535)
+  t.is(port.offset(-1), 65534)
+  t.throws(() => port.offset(1), {
+    instanceOf: RangeError,
+    message: '`65536` is not a valid port',
+  })
+  t.throws(() => Port.parse('port'), {
+    instanceOf: RangeError,
+    message: '`port` is not a valid port',
+  })
+})
+
+test('#[napi(error)] enum classes', (t) => {
+  const err = new ConfigError.NotFound('manually created')
+  t.true(err instanceof ConfigError)
+  t.true(err instanceof Error)
+  t.is(err.message, 'manually created')
+  t.is(String(err), 'NotFound: manually created')
+  t.true(err.stack?.startsWith('NotFound: manually created'))
+  t.true(Object.getPrototypeOf(ConfigError.NotFound) === ConfigError)
+})
+
+test('#[napi(error)] enum variants with the same name', (t) => {
+  const err = t.throws(() => readCache('user'), {
+    instanceOf: CacheError.NotFound,
+    name: 'NotFound',
+    message: 'cache entry `user` is not found',
+  })
+  t.is(err.key, 'user')
+  t.false(err instanceof ConfigError)
+  t.not(CacheError.NotFound, ConfigError.NotFound)
+  t.deepEqual(Object.keys(CacheError), [])
+})
+
+test('panic in fn', (t) => {
+  t.throws(() => panicInFn(), {
+    message: /^panic from Rust code: Don't panic, at .*error\.rs:\d+:\d+$/,
+  })
+})
+
+test('abort on panic in fn', (t) => {
+  const p = exec('node ./abort-on-panic.js', {
+    cwd: __dirname,
+  })
+  let stderr = Buffer.from([])
+  p.stderr?.on('data', (data) => {
+    stderr = Buffer.concat([stderr, Buffer.from(data)])
+  })
+  return new Promise<void>((resolve) => {
+    p.on('exit', (code, signal) => {
+      t.true(signal === 'SIGABRT' || (code !== null && code !== 0))
+      t.true(stderr.toString('utf8').includes(`Don't panic, abort`))
+      resolve()
+    })
+  })
+})
+
+test('panic in async fn', async (t) => {
+  await t.throwsAsync(() => panicInAsyncFn(), {
+    message: /^panic from Rust code: Don't panic in async fn/,
+  })
+})
+
+test('panic in Task', async (t) => {
+  await t.throwsAsync(() => panicInTask(), {
+    message: /^panic from Rust code: Don't panic in Task/,
+  })
+})
+
 test('function ts type override', (t) => {
   t.deepEqual(tsRename({ foo: 1, bar: 2, baz: 2 }), ['foo', 'bar', 'baz'])
 })
@@ -460,6 +874,134 @@ test('async move', async (t) => {
   t.is(await asyncMultiTwo(2), 4)
 })
 
+test('configured tokio runtime', async (t) => {
+  t.is(await getTokioThreadName(), 'napi-examples-worker')
+})
+
+test('custom executor', async (t) => {
+  const spawnedFutures = getSpawnedFutures()
+  await Promise.all([asyncMultiTwo(1), getTokioThreadName()])
+  t.is(getSpawnedFutures(), spawnedFutures + 2)
+})
+
+test('spawn local future on the libuv event loop', async (t) => {
+  const calls: number[] = []
+  const results = await Promise.all([
+    callAfter(() => {
+      calls.push(50)
+      return 'slow'
+    }, 50),
+    callAfter(() => {
+      calls.push(10)
+      return 'fast'
+    }, 10),
+  ])
+  t.deepEqual(results, ['slow', 'fast'])
+  t.deepEqual(calls, [10, 50])
+  await t.throwsAsync(
+    () =>
+      callAfter(() => {
+        throw new Error('boom')
+      }, 1),
+    { message: 'boom' },
+  )
+})
+
+test('stream as async iterable', async (t) => {
+  const items = []
+  for await (const item of countStream(4)) {
+    items.push(item)
+  }
+  t.deepEqual(items, [0, 1, 2, 3])
+
+  const iterator = countStream(4, 2)[Symbol.asyncIterator]()
+  t.deepEqual(await iterator.next(), { value: 0, done: false })
+  t.deepEqual(await iterator.next(), { value: 1, done: false })
+  await t.throwsAsync(() => iterator.next(), {
+    code: 'GenericFailure',
+    message: 'failed at 2',
+  })
+  t.deepEqual(await iterator.next(), { done: true })
+})
+
+test('cancel stream with return', async (t) => {
+  const droppedStreams = getDroppedStreams()
+  const numbers = []
+  for await (const n of naturalNumbers()) {
+    numbers.push(n)
+    if (numbers.length === 3) {
+      break
+    }
+  }
+  t.deepEqual(numbers, [0, 1, 2])
+  t.is(getDroppedStreams(), droppedStreams + 1)
+})
+
+test('cancel pending stream with return', async (t) => {
+  const droppedStreams = getDroppedStreams()
+  const iterator = pendingStream()[Symbol.asyncIterator]()
+  const next = iterator.next()
+  t.deepEqual(await iterator.return(), { done: true })
+  t.deepEqual(await next, { done: true })
+  t.deepEqual(await iterator.next(), { done: true })
+  t.is(getDroppedStreams(), droppedStreams + 1)
+})
+
+test('async iterable as stream', async (t) => {
+  async function* numbers() {
+    yield 1
+    yield 2
+    yield 3
+  }
+  t.is(await sumAsyncIterable(numbers()), 6)
+  t.is(await sumAsyncIterable(countStream(5)), 10)
+  const readable = Readable.from([Buffer.from('hello '), Buffer.from('world')])
+  t.is((await concatAsyncIterable(readable)).toString(), 'hello world')
+
+  async function* failing() {
+    yield 1
+    throw new Error('boom')
+  }
+  await t.throwsAsync(() => sumAsyncIterable(failing()), { message: 'boom' })
+  t.throws(() => sumAsyncIterable({} as any), {
+    code: 'InvalidArg',
+    message: 'Value is not async iterable',
+  })
+
+  let finished = false
+  async function* infinite() {
+    try {
+      let i = 0
+      while (true) {
+        yield i++
+      }
+    } finally {
+      finished = true
+    }
+  }
+  t.deepEqual(await takeFromAsyncIterable(infinite(), 3), [0, 1, 2])
+  await new Promise((resolve) => setTimeout(resolve, 50))
+  t.true(finished)
+})
+
+test('async class methods', async (t) => {
+  const counter = new AsyncCounter(1)
+  t.deepEqual(await Promise.all([counter.read(), counter.read()]), [1, 1])
+  const pending = counter.add(2)
+  t.throws(() => counter.add(1), {
+    code: 'GenericFailure',
+    message:
+      "Can't call async method `add` while another async method on the same instance is pending",
+  })
+  t.throws(() => counter.read(), {
+    code: 'GenericFailure',
+    message:
+      "Can't call async method `read` while an async `&mut self` method on the same instance is pending",
+  })
+  t.is(await pending, 3)
+  t.is(await counter.add(1), 4)
+})
+
 test('buffer passthrough', async (t) => {
   const fixture = Buffer.from('hello world')
   const ret = await bufferPassThrough(fixture)
@@ -559,6 +1101,96 @@ AbortSignalTest('abort resolved task', async (t) => {
   t.pass('should not throw')
 })
 
+AbortSignalTest('abort async fn', async (t) => {
+  const abortedFutures = getAbortedFutures()
+  const ctrl = new AbortController()
+  const promise = waitForAbort(ctrl.signal)
+  ctrl.abort()
+  const err = await t.throwsAsync(promise)
+  t.is(err!.message, 'AbortError')
+  t.is(getAbortedFutures(), abortedFutures + 1)
+})
+
+AbortSignalTest('async fn with aborted signal', async (t) => {
+  const ctrl = new AbortController()
+  ctrl.abort()
+  const err = await t.throwsAsync(waitForAbort(ctrl.signal))
+  t.is(err!.message, 'AbortError')
+})
+
+AbortSignalTest('observe abort from async fn', async (t) => {
+  t.is(await yieldWithSignal(1), 1)
+  const observedAborts = getObservedAborts()
+  const ctrl = new AbortController()
+  t.is(await yieldWithSignal(2, ctrl.signal), 2)
+  ctrl.abort()
+  await new Promise((resolve) => setTimeout(resolve, 50))
+  t.is(getObservedAborts(), observedAborts + 1)
+})
+
+AbortSignalTest('share AbortSignal between calls', async (t) => {
+  const ctrl = new AbortController()
+  let onabortCalled = 0
+  ctrl.signal.onabort = () => {
+    onabortCalled++
+  }
+  const promises = [
+    withAbortController(1, 2, ctrl.signal),
+    withAbortController(3, 4, ctrl.signal),
+    waitForAbort(ctrl.signal),
+  ]
+  ctrl.abort()
+  for (const promise of promises) {
+    const err = await t.throwsAsync(promise)
+    t.is(err!.message, 'AbortError')
+  }
+  t.is(onabortCalled, 1)
+})
+
+AbortSignalTest('read AbortSignal', (t) => {
+  const ctrl = new AbortController()
+  t.false(throwIfAborted(ctrl.signal))
+  const reason = new Error('stop')
+  ctrl.abort(reason)
+  t.is(
+    t.throws(() => throwIfAborted(ctrl.signal)),
+    reason,
+  )
+})
+
+AbortSignalTest('listen to AbortSignal', (t) => {
+  const abortCallbacks = getAbortCallbacks()
+  const ctrl = new AbortController()
+  listenToAbort(ctrl.signal)
+  listenToAbort(ctrl.signal)
+  t.is(getAbortCallbacks(), abortCallbacks)
+  t.deepEqual(Object.keys(ctrl.signal), [])
+  ctrl.abort()
+  t.is(getAbortCallbacks(), abortCallbacks + 2)
+  listenToAbort(ctrl.signal)
+  t.is(getAbortCallbacks(), abortCallbacks + 3)
+})
+
+AbortSignalTest('poll AbortSignal from background thread', async (t) => {
+  const ctrl = new AbortController()
+  const promise = pollUntilAborted(ctrl.signal)
+  setTimeout(() => ctrl.abort(), 10)
+  t.true(await promise)
+})
+
+test('report progress from async task', async (t) => {
+  const progress: number[] = []
+  const result = await countWithProgress(10, ({ current, total }) => {
+    t.is(total, 10)
+    progress.push(current)
+  })
+  t.is(result, 10)
+  t.deepEqual(
+    progress,
+    Array.from({ length: 10 }, (_, i) => i + 1),
+  )
+})
+
 const BigIntTest = typeof BigInt !== 'undefined' ? test : test.skip
 
 BigIntTest('BigInt add', (t) => {
@@ -662,6 +1294,16 @@ Napi4Test('Promise should reject raw error in rust', async (t) => {
   t.is(err, fxError)
 })
 
+Napi4Test('Promise rejection could be dropped in rust', async (t) => {
+  t.is(await asyncPlus100Or(Promise.resolve(1), 0), 101)
+  const results = await Promise.all(
+    Array.from({ length: 100 }, () =>
+      asyncPlus100Or(Promise.reject(new Error('dropped')), 7),
+    ),
+  )
+  t.deepEqual(results, Array(100).fill(7))
+})
+
 const Napi5Test = Number(process.versions.napi) >= 5 ? test : test.skip
 
 Napi5Test('Date test', (t) => {
@@ -16,8 +16,24 @@ export function getWords(): Array<string>
 export function getNums(): Array<number>
 export function sumNums(nums: Array<number>): number
 export function toJsObj(): object
+export function sumIterable(iterable: Iterable<number>): number
+export function takeFromIterable(iterable: Iterable<string>, count: number): Array<string>
+export function getSpawnedFutures(): number
 export function readFileAsync(path: string): Promise<Buffer>
 export function asyncMultiTwo(arg: number): Promise<number>
+export function getTokioThreadName(): Promise<string | null>
+export function countStream(to: number, failAt?: number | undefined | null): AsyncIterable<number>
+export function naturalNumbers(): AsyncIterable<number>
+export function pendingStream(): AsyncIterable<number>
+export function getDroppedStreams(): number
+export function sumAsyncIterable(iterable: AsyncIterable<number>): Promise<number>
+export function concatAsyncIterable(iterable: AsyncIterable<Buffer>): Promise<Buffer>
+export function takeFromAsyncIterable(iterable: AsyncIterable<number>, count: number): Promise<Array<number>>
+export function callAfter(callback: (...args: any[]) => any, ms: number): Promise<unknown>
+export function waitForAbort(signal: AbortSignal): Promise<number>
+export function getAbortedFutures(): number
+export function yieldWithSignal(value: number, signal?: AbortSignal | undefined | null): Promise<number>
+export function getObservedAborts(): number
 export function bigintAdd(a: bigint, b: bigint): bigint
 export function createBigInt(): bigint
 export function createBigIntI64(): bigint
@@ -31,6 +47,13 @@ export function optionOnly(callback: (arg0?: string | undefined | null) => void)
 export function readFile(callback: (arg0: Error | undefined, arg1?: string | undefined | null) => void): void
 export function returnJsFunction(): (...args: any[]) => any
 export function callbackReturnPromise<T>(functionInput: () => T | Promise<T>, callback: (err: Error | null, result: T) => void): T | Promise<T>
+export function callAndRethrow(callback: (...args: any[]) => any): void
+export interface CaughtException {
+  message: string
+  stack?: string
+}
+export function catchJsException(callback: () => void): CaughtException | null
+export function getAnimalName(animal: Animal): string
 export function dateToNumber(input: Date): number
 export function chronoDateToMillis(input: Date): number
 export function chronoDateAdd1Minute(input: Date): Date
@@ -73,11 +96,35 @@ export const enum CustomNumEnum {
   Ten = 10
 }
 export function enumToI32(e: CustomNumEnum): number
+/** File access modes */
+export type AccessMode = 'read' | 'write' | 'read+write'
+export type LineEnding = 'lf' | 'cr-lf'
+export function accessModeToFlags(mode: AccessMode): number
+export function oppositeLineEnding(ending: LineEnding): LineEnding
+/** Internally tagged, the variant fields live next to the `type` field */
+export type Command = { type: 'Move', x: number, y: number } | { type: 'Say', text: string, to?: string } | { type: 'quit' }
+/** Adjacently tagged, the variant data lives in the `value` field */
+export type Shape = { kind: 'circle', value: number } | { kind: 'rectangle', value: [number, number] } | { kind: 'polygon', value: { points: Array<number>, closed: boolean } } | { kind: 'empty' }
+export function runCommand(command: Command): Command
+export function scaleShape(shape: Shape, factor: number): Shape
 export function throwError(): void
+export function panicInFn(): void
+export function panicAndAbort(): void
+export function panicInAsyncFn(): Promise<number>
+export function panicInTask(): Promise<number>
+export function throwTypeErrorWithCode(): void
+export function throwErrorWithCause(): void
+export function loadConfig(content: string): string
+export function parsePort(input: string): number
+export function parsePortAsync(input: string): Promise<number>
+export function parsePortInTask(input: string): Promise<number>
+export function readCache(key: string): string
 export function createExternal(size: number): ExternalObject<number>
 export function createExternalString(content: string): ExternalObject<string>
 export function getExternal(external: ExternalObject<number>): number
 export function mutateExternal(external: ExternalObject<number>, newVal: number): void
+export function sumRest(...nums: Array<number>): number
+export function formatMessage(template: string, ...args: Array<string | number>): string
 export function validateArray(arr: Array<number>): number
 export function validateBuffer(b: Buffer): number
 export function validateTypedArray(input: Uint8Array): number
@@ -97,12 +144,26 @@ export function validateSymbol(s: symbol): boolean
 export function tsRename(a: { foo: number }): string[]
 export function overrideIndividualArgOnFunction(notOverridden: string, f: () => string, notOverridden2: number): string
 export function overrideIndividualArgOnFunctionWithCbArg(callback: (town: string, name?: string | undefined | null) => string, notOverridden: number): object
+export function fibNumbers(count: number): Iterable<number>
+export function parseNumbers(input: string): Iterable<number>
+export function splitWords(text: string): Iterable<string>
 export function xxh64Alias(input: Buffer): bigint
 export function getMapping(): Record<string, number>
 export function sumMapping(nums: Record<string, number>): number
 export function mapOption(val?: number | undefined | null): number | null
 export function returnNull(): null
 export function returnUndefined(): void
+/**
+ * @param [text="napi"]
+ * @param [times=2]
+ */
+export function repeatWithDefault(text?: string | null, times?: number | null): string
+/** @param [width=4] */
+export function padWithDefault(text: string, width?: number | undefined | null): string
+/** @param [factor=1.5] */
+export function scaleWithDefault(factor: number | null | undefined, value: number): number
+/** @param [step=1] */
+export function roundWithDefault(value: number, step?: number | undefined | null): number
 export function add(a: number, b: number): number
 export function fibonacci(n: number): number
 export function listObjKeys(obj: object): Array<string>
@@ -135,7 +196,30 @@ export interface TsTypeChanged {
 }
 export function createObjWithProperty(): { value: ArrayBuffer, get getter(): number }
 export function getterFromObj(): number
+export function getThisName(): string | null
+export function increaseThisCount(delta: number): number
+/** Parse the number from string */
+export function parseNumber(input: string, radix?: number | undefined | null): number
+/** Truncate the number */
+export function parseNumber(input: number): number
+/** Parse the number from bytes */
+export function parseNumber(input: Buffer): number
+/** Add one to the awaited number */
+export function plusOne(input: Promise<number>): Promise<number>
+/** Add one to the number */
+export function plusOne(input: number): number
+export interface Pet {
+  name: string
+}
+export function describePet(dog: Dog): string
+export function describePet(bird: Bird): string
+export function describePet(pet: Pet): string
+/** The characters of the line ending */
+export function lineBreak(ending: LineEnding): string
+/** Use the string as the line break */
+export function lineBreak(input: string): string
 export function asyncPlus100(p: Promise<number>): Promise<number>
+export function asyncPlus100Or(p: Promise<number>, fallback: number): Promise<number>
 /** This is an interface for package.json */
 export interface PackageJson {
   name: string
@@ -156,6 +240,15 @@ export function setSymbolInObj(symbol: symbol): object
 export function createSymbol(): symbol
 export function withoutAbortController(a: number, b: number): Promise<number>
 export function withAbortController(a: number, b: number, signal: AbortSignal): Promise<number>
+export function throwIfAborted(signal: AbortSignal): boolean
+export function listenToAbort(signal: AbortSignal): void
+export function getAbortCallbacks(): number
+export function pollUntilAborted(signal: AbortSignal): Promise<boolean>
+export interface CountProgress {
+  current: number
+  total: number
+}
+export function countWithProgress(total: number, onProgress: (progress: CountProgress) => void): Promise<number>
 export function callThreadsafeFunction(callback: (...args: any[]) => any): void
 export function threadsafeFunctionThrowError(cb: (...args: any[]) => any): void
 export function threadsafeFunctionFatalMode(cb: (...args: any[]) => any): void
@@ -169,6 +262,11 @@ export function mutateTypedArray(input: Float32Array): void
 export function derefUint8Array(a: Uint8Array, b: Uint8ClampedArray): number
 export function bufferPassThrough(buf: Buffer): Promise<Buffer>
 export function asyncReduceBuffer(buf: Buffer): Promise<number>
+export class AsyncCounter {
+  constructor(value: number)
+  read(): Promise<number>
+  add(delta: number): Promise<number>
+}
 /**
  * `constructor` option for `struct` requires all fields to be public,
  * otherwise tag impl fn as constructor
@@ -202,6 +300,13 @@ export class Animal {
   returnOtherClassWithCustomConstructor(): Bird
   overrideIndividualArgOnMethod(normalTy: string, overriddenTy: {n: string}): Bird
 }
+/** `Animal` methods could be called on `Cat` instances */
+export class Cat extends Animal {
+  readonly lives: number
+  constructor(name: string)
+  loseLife(): number
+  introduce(): string
+}
 export class Dog {
   name: string
   constructor(name: string)
@@ -261,6 +366,15 @@ export class ClassWithFactory {
 export class JsClassForEither {
   constructor()
 }
+/** Errors of loading the config file */
+export class ConfigError extends Error { }
+export class Port {
+  value: number
+  static parse(input: string): Port
+  offset(delta: number): number
+}
+/** Shares the `NotFound` variant with `ConfigError` */
+export class CacheError extends Error { }
 export class Fib {
   [Symbol.iterator](): Iterator<number, void, number>
   constructor()
@@ -275,6 +389,11 @@ export class Fib3 {
   constructor(current: number, next: number)
   [Symbol.iterator](): Iterator<number, void, number>
 }
+/** Paging cursor yields the ids page by page */
+export class PageCursor {
+  [Symbol.asyncIterator](): AsyncGenerator<Array<number>, void, void>
+  constructor(totalPages: number, pageSize: number)
+}
 export class JsRepo {
   constructor(dir: string)
   remote(): JsRemote
@@ -298,6 +417,25 @@ export type AnotherCSSStyleSheet = AnotherCssStyleSheet
 export class AnotherCssStyleSheet {
   get rules(): CssRuleList
 }
+export namespace ConfigError {
+  export class NotFound extends ConfigError {
+    path: string
+  }
+  export class InvalidValue extends ConfigError {
+    /** The line number of the invalid value */
+    line: number
+    reason: string
+  }
+  export class UnsupportedVersion extends ConfigError {
+    field0: number
+  }
+  export class ConfigLockedError extends ConfigError {   }
+}
+export namespace CacheError {
+  export class NotFound extends CacheError {
+    key: string
+  }
+}
 export namespace xxh3 {
   export const ALIGNMENT: number
   export function xxh3_64(input: Buffer): bigint
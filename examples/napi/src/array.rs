@@ -1,4 +1,4 @@
-use napi::{Env, JsObject};
+use napi::{bindgen_prelude::JsIterator, Env, JsObject};
 
 #[napi]
 pub fn get_words() -> Vec<&'static str> {
@@ -23,3 +23,13 @@ fn to_js_obj(env: Env) -> napi::Result<JsObject> {
   arr.insert(42)?;
   arr.coerce_to_object()
 }
+
+#[napi]
+fn sum_iterable(iterable: JsIterator<u32>) -> napi::Result<u32> {
+  iterable.sum()
+}
+
+#[napi]
+fn take_from_iterable(iterable: JsIterator<String>, count: u32) -> napi::Result<Vec<String>> {
+  iterable.take(count as usize).collect()
+}
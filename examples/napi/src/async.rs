@@ -1,7 +1,37 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
 use futures::prelude::*;
 use napi::bindgen_prelude::*;
+use napi::executor::{Executor, SpawnedFuture, TokioExecutor};
 use napi::tokio::{self, fs};
 
+#[napi::module_init]
+fn configure_tokio_runtime() {
+  let mut builder = tokio::runtime::Builder::new_multi_thread();
+  builder
+    .worker_threads(2)
+    .thread_name("napi-examples-worker")
+    .enable_all();
+  napi::tokio_runtime::configure(builder).expect("Configure tokio runtime failed");
+}
+
+static SPAWNED_FUTURES: AtomicU32 = AtomicU32::new(0);
+
+#[napi::module_init]
+fn set_counting_executor() {
+  napi::executor::set_executor(|fut: SpawnedFuture| {
+    SPAWNED_FUTURES.fetch_add(1, Ordering::SeqCst);
+    TokioExecutor.spawn(fut);
+  })
+  .expect("Set executor failed");
+}
+
+#[napi]
+fn get_spawned_futures() -> u32 {
+  SPAWNED_FUTURES.load(Ordering::SeqCst)
+}
+
 #[napi]
 async fn read_file_async(path: String) -> Result<Buffer> {
   fs::read(path)
@@ -21,3 +51,152 @@ async fn async_multi_two(arg: u32) -> Result<u32> {
     .await
     .unwrap()
 }
+
+#[napi]
+pub struct AsyncCounter {
+  value: u32,
+}
+
+#[napi]
+impl AsyncCounter {
+  #[napi(constructor)]
+  pub fn new(value: u32) -> Self {
+    AsyncCounter { value }
+  }
+
+  #[napi]
+  pub async fn read(&self) -> u32 {
+    tokio::task::yield_now().await;
+    self.value
+  }
+
+  #[napi]
+  pub async fn add(&mut self, delta: u32) -> u32 {
+    tokio::task::yield_now().await;
+    self.value += delta;
+    self.value
+  }
+}
+
+static DROPPED_STREAMS: AtomicU32 = AtomicU32::new(0);
+
+struct StreamDropGuard;
+
+impl Drop for StreamDropGuard {
+  fn drop(&mut self) {
+    DROPPED_STREAMS.fetch_add(1, Ordering::SeqCst);
+  }
+}
+
+#[napi]
+async fn get_tokio_thread_name() -> Option<String> {
+  std::thread::current().name().map(|name| name.to_owned())
+}
+
+#[napi]
+fn count_stream(to: u32, fail_at: Option<u32>) -> impl Stream<Item = Result<u32>> {
+  stream::iter(0..to).map(move |n| match fail_at {
+    Some(fail_at) if n == fail_at => Err(Error::new(
+      Status::GenericFailure,
+      format!("failed at {}", n),
+    )),
+    _ => Ok(n),
+  })
+}
+
+#[napi]
+fn natural_numbers() -> impl Stream<Item = Result<u32>> {
+  stream::unfold((0, StreamDropGuard), |(n, guard)| async move {
+    tokio::task::yield_now().await;
+    Some((Ok(n), (n + 1, guard)))
+  })
+}
+
+#[napi]
+fn pending_stream() -> impl Stream<Item = Result<u32>> {
+  let guard = StreamDropGuard;
+  stream::once(async move {
+    let _guard = guard;
+    future::pending().await
+  })
+}
+
+#[napi]
+fn get_dropped_streams() -> u32 {
+  DROPPED_STREAMS.load(Ordering::SeqCst)
+}
+
+#[napi]
+async fn sum_async_iterable(iterable: JsAsyncIterable<u32>) -> Result<u32> {
+  iterable
+    .try_fold(0, |sum, n| async move { Ok(sum + n) })
+    .await
+}
+
+#[napi]
+async fn concat_async_iterable(iterable: JsAsyncIterable<Buffer>) -> Result<Buffer> {
+  iterable
+    .try_fold(Vec::new(), |mut data, chunk| async move {
+      data.extend_from_slice(&chunk);
+      Ok(data)
+    })
+    .await
+    .map(Buffer::from)
+}
+
+#[napi]
+async fn take_from_async_iterable(iterable: JsAsyncIterable<u32>, count: u32) -> Result<Vec<u32>> {
+  iterable.take(count as usize).try_collect().await
+}
+
+#[napi(ts_return_type = "Promise<unknown>")]
+fn call_after(env: Env, callback: JsFunction, ms: u32) -> Result<Object> {
+  let mut callback_ref = env.create_reference(callback)?;
+  env.spawn_local(async move {
+    let slept = napi::uv::sleep(&env, Duration::from_millis(ms as u64)).await;
+    let callback = env.get_reference_value::<JsFunction>(&callback_ref);
+    callback_ref.unref(env)?;
+    slept?;
+    callback?.call_without_args(None)
+  })
+}
+
+static ABORTED_FUTURES: AtomicU32 = AtomicU32::new(0);
+static OBSERVED_ABORTS: AtomicU32 = AtomicU32::new(0);
+
+struct AbortDropGuard;
+
+impl Drop for AbortDropGuard {
+  fn drop(&mut self) {
+    ABORTED_FUTURES.fetch_add(1, Ordering::SeqCst);
+  }
+}
+
+#[napi]
+async fn wait_for_abort(_signal: AbortSignal) -> u32 {
+  let _guard = AbortDropGuard;
+  future::pending::<()>().await;
+  0
+}
+
+#[napi]
+fn get_aborted_futures() -> u32 {
+  ABORTED_FUTURES.load(Ordering::SeqCst)
+}
+
+#[napi]
+async fn yield_with_signal(value: u32, signal: Option<AbortSignal>) -> u32 {
+  if let Some(signal) = signal {
+    spawn(async move {
+      signal.cancelled().await;
+      OBSERVED_ABORTS.fetch_add(1, Ordering::SeqCst);
+    });
+  }
+  tokio::task::yield_now().await;
+  value
+}
+
+#[napi]
+fn get_observed_aborts() -> u32 {
+  OBSERVED_ABORTS.load(Ordering::SeqCst)
+}
@@ -3,7 +3,7 @@ use std::env;
 use napi::{
   bindgen_prelude::*,
   threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode},
-  JsUnknown,
+  JsFunction, JsUnknown,
 };
 
 #[napi]
@@ -80,3 +80,23 @@ fn callback_return_promise<T: Fn() -> Result<JsUnknown>>(
     Ok(ret)
   }
 }
+
+#[napi]
+fn call_and_rethrow(callback: JsFunction) -> Result<()> {
+  callback.call_without_args(None)?;
+  Ok(())
+}
+
+#[napi(object)]
+pub struct CaughtException {
+  pub message: String,
+  pub stack: Option<String>,
+}
+
+#[napi]
+fn catch_js_exception<T: Fn() -> Result<()>>(callback: T) -> Option<CaughtException> {
+  callback().err().map(|e| CaughtException {
+    message: e.reason.clone(),
+    stack: e.js_stack().map(|stack| stack.to_owned()),
+  })
+}
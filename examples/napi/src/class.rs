@@ -99,6 +99,42 @@ impl Animal {
   }
 }
 
+/// `Animal` methods could be called on `Cat` instances
+#[napi(extends = Animal)]
+pub struct Cat {
+  base: Animal,
+  #[napi(readonly)]
+  pub lives: u32,
+}
+
+#[napi]
+impl Cat {
+  #[napi(constructor)]
+  pub fn new(name: String) -> Self {
+    Cat {
+      base: Animal::new(Kind::Cat, name),
+      lives: 9,
+    }
+  }
+
+  #[napi]
+  pub fn lose_life(&mut self) -> u32 {
+    self.lives = self.lives.saturating_sub(1);
+    self.lives
+  }
+
+  #[napi]
+  pub fn introduce(&self) -> String {
+    let animal: &Animal = self.as_ref();
+    format!("{} with {} lives", animal.whoami(), self.lives)
+  }
+}
+
+#[napi]
+fn get_animal_name(animal: &Animal) -> String {
+  animal.name.clone()
+}
+
 #[napi(constructor)]
 pub struct Dog {
   pub name: String,
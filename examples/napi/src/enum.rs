@@ -38,3 +38,87 @@ pub enum SkippedEnums {
   Two,
   Tree,
 }
+
+/// File access modes
+#[napi(string_enum, rename_all = "lowercase")]
+pub enum AccessMode {
+  Read,
+  Write,
+  #[napi(js_name = "read+write")]
+  ReadWrite,
+}
+
+#[napi(string_enum, rename_all = "kebab-case")]
+pub enum LineEnding {
+  Lf,
+  CrLf,
+}
+
+#[napi]
+fn access_mode_to_flags(mode: AccessMode) -> u32 {
+  match mode {
+    AccessMode::Read => 0,
+    AccessMode::Write => 1,
+    AccessMode::ReadWrite => 2,
+  }
+}
+
+#[napi]
+fn opposite_line_ending(ending: LineEnding) -> LineEnding {
+  match ending {
+    LineEnding::Lf => LineEnding::CrLf,
+    LineEnding::CrLf => LineEnding::Lf,
+  }
+}
+
+/// Internally tagged, the variant fields live next to the `type` field
+#[napi]
+pub enum Command {
+  Move {
+    x: i32,
+    y: i32,
+  },
+  Say {
+    text: String,
+    to: Option<String>,
+  },
+  #[napi(js_name = "quit")]
+  Quit,
+}
+
+/// Adjacently tagged, the variant data lives in the `value` field
+#[napi(tag = "kind", content = "value", rename_all = "camelCase")]
+pub enum Shape {
+  Circle(f64),
+  Rectangle(f64, f64),
+  Polygon { points: Vec<u32>, closed: bool },
+  Empty,
+}
+
+#[napi]
+fn run_command(command: Command) -> Command {
+  match command {
+    Command::Move { x, y } => Command::Move { x: x * 2, y: y * 2 },
+    Command::Say { text, to } => Command::Say {
+      text: text.to_uppercase(),
+      to,
+    },
+    Command::Quit => Command::Quit,
+  }
+}
+
+#[napi]
+fn scale_shape(shape: Shape, factor: f64) -> Shape {
+  match shape {
+    Shape::Circle(r) => Shape::Circle(r * factor),
+    Shape::Rectangle(w, h) => Shape::Rectangle(w * factor, h * factor),
+    Shape::Polygon { points, closed } => Shape::Polygon {
+      points: points
+        .into_iter()
+        .map(|p| (p as f64 * factor) as u32)
+        .collect(),
+      closed,
+    },
+    Shape::Empty => Shape::Empty,
+  }
+}
@@ -4,3 +4,173 @@ use napi::bindgen_prelude::*;
 fn throw_error() -> Result<()> {
   Err(Error::new(Status::InvalidArg, "Manual Error".to_owned()))
 }
+
+#[napi]
+fn panic_in_fn() {
+  panic!("Don't panic");
+}
+
+#[napi(abort_on_panic)]
+fn panic_and_abort() {
+  panic!("Don't panic, abort");
+}
+
+#[napi]
+async fn panic_in_async_fn() -> Result<u32> {
+  panic!("Don't panic in async fn");
+}
+
+struct PanicTask;
+
+#[napi]
+impl Task for PanicTask {
+  type Output = u32;
+  type JsValue = u32;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    panic!("Don't panic in Task");
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+fn panic_in_task() -> AsyncTask<PanicTask> {
+  AsyncTask::new(PanicTask)
+}
+
+#[napi]
+fn throw_type_error_with_code() -> Result<()> {
+  Err(
+    Error::new(Status::InvalidArg, "Expect a positive number".to_owned())
+      .with_kind(ErrorKind::TypeError)
+      .with_code("ERR_NEGATIVE_NUMBER"),
+  )
+}
+
+#[napi]
+fn throw_error_with_cause() -> Result<()> {
+  Err(
+    Error::from_reason("Failed to read config").with_cause(
+      Error::from_reason("Config file is too large")
+        .with_kind(ErrorKind::RangeError)
+        .with_code("ERR_FILE_TOO_LARGE"),
+    ),
+  )
+}
+
+/// Errors of loading the config file
+#[napi(error)]
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+  #[error("config file `{path}` is not found")]
+  NotFound { path: String },
+  #[error("invalid value at line {line}: {reason}")]
+  InvalidValue {
+    /// The line number of the invalid value
+    line: u32,
+    reason: String,
+  },
+  #[error("unsupported config version {0}")]
+  UnsupportedVersion(u32),
+  #[napi(js_name = "ConfigLockedError")]
+  #[error("config file is locked")]
+  Locked,
+}
+
+#[napi]
+fn load_config(content: String) -> std::result::Result<String, ConfigError> {
+  match content.as_str() {
+    "" => Err(ConfigError::NotFound {
+      path: "napi.config.json".to_owned(),
+    }),
+    "locked" => Err(ConfigError::Locked),
+    "version: 2" => Err(ConfigError::UnsupportedVersion(2)),
+    c if c.contains(':') => Ok(c.to_owned()),
+    _ => Err(ConfigError::InvalidValue {
+      line: 1,
+      reason: "expect `key: value`".to_owned(),
+    }),
+  }
+}
+
+/// A plain Rust error without `#[napi]`, thrown to JavaScript through `From<PortError> for Error`
+#[derive(Debug)]
+pub struct PortError(String);
+
+impl From<PortError> for Error {
+  fn from(err: PortError) -> Self {
+    Error::new(Status::InvalidArg, err.0).with_kind(ErrorKind::RangeError)
+  }
+}
+
+fn parse_port_str(input: &str) -> std::result::Result<u32, PortError> {
+  input
+    .parse::<u16>()
+    .map(u32::from)
+    .map_err(|_| PortError(format!("`{}` is not a valid port", input)))
+}
+
+#[napi]
+fn parse_port(input: String) -> std::result::Result<u32, PortError> {
+  parse_port_str(&input)
+}
+
+#[napi]
+async fn parse_port_async(input: String) -> std::result::Result<u32, PortError> {
+  parse_port_str(&input)
+}
+
+pub struct PortTask(u32);
+
+#[napi]
+impl Task for PortTask {
+  type Output = u32;
+  type JsValue = u32;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(self.0)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+fn parse_port_in_task(input: String) -> std::result::Result<AsyncTask<PortTask>, PortError> {
+  parse_port_str(&input).map(|port| AsyncTask::new(PortTask(port)))
+}
+
+#[napi]
+pub struct Port {
+  pub value: u32,
+}
+
+#[napi]
+impl Port {
+  #[napi(factory)]
+  pub fn parse(input: String) -> std::result::Result<Self, PortError> {
+    parse_port_str(&input).map(|value| Port { value })
+  }
+
+  #[napi]
+  pub fn offset(&self, delta: i32) -> std::result::Result<u32, PortError> {
+    parse_port_str(&(self.value as i64 + delta as i64).to_string())
+  }
+}
+
+/// Shares the `NotFound` variant with `ConfigError`
+#[napi(error)]
+#[derive(Debug, thiserror::Error)]
+pub enum CacheError {
+  #[error("cache entry `{key}` is not found")]
+  NotFound { key: String },
+}
+
+#[napi]
+fn read_cache(key: String) -> std::result::Result<String, CacheError> {
+  Err(CacheError::NotFound { key })
+}
@@ -0,0 +1,29 @@
+use napi::bindgen_prelude::*;
+
+#[napi]
+fn sum_rest(nums: Rest<u32>) -> u32 {
+  nums.iter().sum()
+}
+
+#[napi]
+fn format_message(template: String, args: Rest<Either<String, f64>>) -> Result<String> {
+  let mut args = args.into_iter();
+  let mut message = String::with_capacity(template.len());
+  let mut parts = template.split("{}").peekable();
+  while let Some(part) = parts.next() {
+    message.push_str(part);
+    if parts.peek().is_some() {
+      match args.next() {
+        Some(Either::A(s)) => message.push_str(&s),
+        Some(Either::B(n)) => message.push_str(&n.to_string()),
+        None => {
+          return Err(Error::new(
+            Status::InvalidArg,
+            "Not enough arguments for the template".to_owned(),
+          ))
+        }
+      }
+    }
+  }
+  Ok(message)
+}
@@ -109,3 +109,84 @@ impl Generator for Fib3 {
     Some(self.current)
   }
 }
+
+/// Paging cursor yields the ids page by page
+#[napi(async_iterator)]
+pub struct PageCursor {
+  page: u32,
+  total_pages: u32,
+  page_size: u32,
+}
+
+#[napi]
+impl AsyncGenerator for PageCursor {
+  type Yield = Vec<u32>;
+  type Next = ();
+  type Return = ();
+
+  fn next(&mut self, _value: Option<Self::Next>) -> AsyncGeneratorFuture<'_, Option<Self::Yield>> {
+    Box::pin(async move {
+      napi::tokio::task::yield_now().await;
+      if self.page >= self.total_pages {
+        return Ok(None);
+      }
+      let start = self.page * self.page_size;
+      self.page += 1;
+      Ok(Some((start..start + self.page_size).collect()))
+    })
+  }
+
+  fn complete(&mut self, _value: Option<Self::Return>) -> AsyncGeneratorFuture<'_, ()> {
+    Box::pin(async move {
+      self.page = self.total_pages;
+      Ok(())
+    })
+  }
+}
+
+#[napi]
+impl PageCursor {
+  #[napi(constructor)]
+  pub fn new(total_pages: u32, page_size: u32) -> Self {
+    PageCursor {
+      page: 0,
+      total_pages,
+      page_size,
+    }
+  }
+}
+
+#[napi]
+fn fib_numbers(count: u32) -> impl Iterator<Item = u32> {
+  std::iter::successors(Some((0u32, 1u32)), |&(current, next)| {
+    Some((next, current + next))
+  })
+  .map(|(current, _)| current)
+  .take(count as usize)
+}
+
+#[napi]
+fn parse_numbers(input: String) -> Result<impl Iterator<Item = u32>> {
+  let numbers = input
+    .split(',')
+    .map(|n| {
+      n.trim().parse::<u32>().map_err(|_| {
+        Error::new(
+          Status::InvalidArg,
+          format!("`{}` is not a number", n.trim()),
+        )
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+  Ok(numbers.into_iter())
+}
+
+#[napi]
+fn split_words(text: String) -> JsIterable<String> {
+  JsIterable::new(
+    text
+      .split_whitespace()
+      .map(|word| word.to_owned())
+      .collect::<Vec<_>>(),
+  )
+}
@@ -24,6 +24,7 @@ mod either;
 mod r#enum;
 mod error;
 mod external;
+mod fn_rest;
 mod fn_strict;
 mod fn_ts_override;
 mod generator;
@@ -32,6 +33,7 @@ mod map;
 mod nullable;
 mod number;
 mod object;
+mod overload;
 mod promise;
 mod reference;
 mod serde;
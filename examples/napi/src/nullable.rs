@@ -12,3 +12,27 @@ fn return_null() -> Null {
 
 #[napi]
 fn return_undefined() -> Undefined {}
+
+#[napi]
+fn repeat_with_default(
+  #[napi(default = "napi")] text: String,
+  #[napi(default = 2)] times: u32,
+) -> String {
+  text.repeat(times as usize)
+}
+
+#[napi]
+fn pad_with_default(text: String, #[napi(default = 4)] width: Option<u32>) -> String {
+  format!("{:>width$}", text, width = width.unwrap_or(0) as usize)
+}
+
+#[napi]
+fn scale_with_default(#[napi(default = 1.5)] factor: f64, value: f64) -> f64 {
+  value * factor
+}
+
+#[napi]
+fn round_with_default(value: f64, #[napi(default = 1)] step: Option<f64>) -> f64 {
+  let step = step.unwrap_or(1.0);
+  (value / step).round() * step
+}
@@ -101,3 +101,15 @@ pub fn create_obj_with_property(env: Env) -> Result<JsObject> {
 fn getter_from_obj() -> u32 {
   42
 }
+
+#[napi]
+fn get_this_name(this: This) -> Result<Option<String>> {
+  this.get("name")
+}
+
+#[napi(strict)]
+fn increase_this_count(mut this: This<Object>, delta: u32) -> Result<u32> {
+  let count = this.get::<_, u32>("count")?.unwrap_or(0) + delta;
+  this.set("count", count)?;
+  Ok(count)
+}
@@ -0,0 +1,70 @@
+use napi::bindgen_prelude::*;
+
+use crate::class::{Bird, Dog};
+use crate::r#enum::LineEnding;
+
+/// Parse the number from string
+#[napi(js_name = "parseNumber", overload)]
+fn parse_number_from_string(input: String, radix: Option<u32>) -> Result<i64> {
+  i64::from_str_radix(input.trim(), radix.unwrap_or(10))
+    .map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+}
+
+/// Truncate the number
+#[napi(js_name = "parseNumber", overload)]
+fn parse_number_from_number(input: f64) -> i64 {
+  input.trunc() as i64
+}
+
+/// Parse the number from bytes
+#[napi(js_name = "parseNumber", overload)]
+fn parse_number_from_buffer(input: Buffer) -> Result<i64> {
+  parse_number_from_string(String::from_utf8_lossy(&input).into_owned(), None)
+}
+
+/// Add one to the awaited number
+#[napi(js_name = "plusOne", overload)]
+async fn plus_one_to_promise(input: Promise<u32>) -> Result<u32> {
+  Ok(input.await? + 1)
+}
+
+/// Add one to the number
+#[napi(js_name = "plusOne", overload)]
+fn plus_one_to_number(input: u32) -> u32 {
+  input + 1
+}
+
+#[napi(object)]
+pub struct Pet {
+  pub name: String,
+}
+
+#[napi(js_name = "describePet", overload)]
+fn describe_dog(dog: &Dog) -> String {
+  format!("Dog: {}", dog.name)
+}
+
+#[napi(js_name = "describePet", overload)]
+fn describe_bird(bird: &Bird) -> String {
+  format!("Bird: {}", bird.name)
+}
+
+#[napi(js_name = "describePet", overload)]
+fn describe_pet(pet: Pet) -> String {
+  format!("Pet: {}", pet.name)
+}
+
+/// The characters of the line ending
+#[napi(js_name = "lineBreak", overload)]
+fn line_break_of_ending(ending: LineEnding) -> String {
+  match ending {
+    LineEnding::Lf => "\n".to_owned(),
+    LineEnding::CrLf => "\r\n".to_owned(),
+  }
+}
+
+/// Use the string as the line break
+#[napi(js_name = "lineBreak", overload)]
+fn line_break_of_string(input: String) -> String {
+  input
+}
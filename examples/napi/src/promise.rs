@@ -5,3 +5,9 @@ pub async fn async_plus_100(p: Promise<u32>) -> Result<u32> {
   let v = p.await?;
   Ok(v + 100)
 }
+
+#[napi]
+pub async fn async_plus_100_or(p: Promise<u32>, fallback: u32) -> u32 {
+  // the rejection is dropped on the worker thread
+  p.await.map_or(fallback, |v| v + 100)
+}
@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 use std::thread::sleep;
 
 use napi::bindgen_prelude::*;
@@ -28,3 +30,90 @@ fn without_abort_controller(a: u32, b: u32) -> AsyncTask<DelaySum> {
 fn with_abort_controller(a: u32, b: u32, signal: AbortSignal) -> AsyncTask<DelaySum> {
   AsyncTask::with_signal(DelaySum(a, b), signal)
 }
+
+#[napi]
+fn throw_if_aborted(signal: AbortSignal) -> Result<bool> {
+  match signal.reason() {
+    Some(reason) => Err(reason),
+    None => Ok(signal.aborted()),
+  }
+}
+
+static ABORT_CALLBACKS: AtomicU32 = AtomicU32::new(0);
+
+#[napi]
+fn listen_to_abort(signal: AbortSignal) {
+  signal.on_abort(|| {
+    ABORT_CALLBACKS.fetch_add(1, Ordering::SeqCst);
+  });
+}
+
+#[napi]
+fn get_abort_callbacks() -> u32 {
+  ABORT_CALLBACKS.load(Ordering::SeqCst)
+}
+
+struct PollUntilAborted(Arc<AtomicBool>);
+
+#[napi]
+impl napi::Task for PollUntilAborted {
+  type Output = bool;
+  type JsValue = bool;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    for _ in 0..1000 {
+      if self.0.load(Ordering::Relaxed) {
+        return Ok(true);
+      }
+      sleep(std::time::Duration::from_millis(5));
+    }
+    Ok(false)
+  }
+
+  fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+fn poll_until_aborted(signal: AbortSignal) -> AsyncTask<PollUntilAborted> {
+  AsyncTask::new(PollUntilAborted(signal.into()))
+}
+
+#[napi(object)]
+pub struct CountProgress {
+  pub current: u32,
+  pub total: u32,
+}
+
+struct CountTo(u32);
+
+#[napi]
+impl napi::ProgressTask for CountTo {
+  type Output = u32;
+  type JsValue = u32;
+  type Progress = CountProgress;
+
+  fn compute(&mut self, ctx: &TaskContext<Self::Progress>) -> Result<Self::Output> {
+    for current in 1..=self.0 {
+      sleep(std::time::Duration::from_millis(1));
+      ctx.report(CountProgress {
+        current,
+        total: self.0,
+      });
+    }
+    Ok(self.0)
+  }
+
+  fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+#[napi]
+fn count_with_progress(
+  total: u32,
+  on_progress: ProgressCallback<CountProgress>,
+) -> AsyncProgressTask<CountTo> {
+  AsyncProgressTask::new(CountTo(total), on_progress)
+}
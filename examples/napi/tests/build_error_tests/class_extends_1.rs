@@ -0,0 +1,17 @@
+//! This is testing that the first field of the derived class must be the base class
+
+use napi_derive::napi;
+
+pub struct Animal {
+  pub name: String,
+}
+
+#[napi(extends = Animal)]
+pub struct Cat {
+  pub lives: u32,
+  base: Animal,
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
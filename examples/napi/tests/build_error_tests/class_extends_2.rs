@@ -0,0 +1,17 @@
+//! This is testing that the base class must be a #[napi] struct defined before the derived class
+
+use napi_derive::napi;
+
+#[napi(extends = Animal)]
+pub struct Cat {
+  base: Animal,
+  pub lives: u32,
+}
+
+pub struct Animal {
+  pub name: String,
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
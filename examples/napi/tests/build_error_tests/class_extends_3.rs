@@ -0,0 +1,17 @@
+//! This is testing that the derived class can't be #[repr(transparent)]
+
+use napi_derive::napi;
+
+pub struct Animal {
+  pub name: String,
+}
+
+#[napi(extends = Animal)]
+#[repr(transparent)]
+pub struct Cat {
+  base: Animal,
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
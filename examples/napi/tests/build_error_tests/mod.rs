@@ -1,5 +1,13 @@
 //! Include the test files here so they can be formatted properly with `cargo fmt`
 
+pub mod class_extends_1;
+pub mod class_extends_2;
+pub mod class_extends_3;
+pub mod overload_1;
+pub mod rest_1;
+pub mod string_enum_1;
+pub mod string_enum_2;
+pub mod tagged_enum_1;
 pub mod ts_arg_type_1;
 pub mod ts_arg_type_2;
 pub mod ts_arg_type_3;
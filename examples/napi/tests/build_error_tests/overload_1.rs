@@ -0,0 +1,17 @@
+//! This is testing that `#[napi(overload)]` is not allowed on methods
+
+use napi_derive::napi;
+
+pub struct Parser {}
+
+#[napi]
+impl Parser {
+  #[napi(overload)]
+  pub fn parse(&self, input: String) -> u32 {
+    input.len() as u32
+  }
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
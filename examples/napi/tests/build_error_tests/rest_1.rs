@@ -0,0 +1,12 @@
+//! This is testing that `Rest<T>` must be the last argument
+
+use napi_derive::napi;
+
+#[napi]
+pub fn join(parts: napi::bindgen_prelude::Rest<String>, separator: String) -> String {
+  parts.join(&separator)
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
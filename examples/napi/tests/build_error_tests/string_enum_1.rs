@@ -0,0 +1,13 @@
+//! This is testing that `rename_all` only accepts the rules of `#[serde(rename_all = "...")]`
+
+use napi_derive::napi;
+
+#[napi(string_enum, rename_all = "Title Case")]
+pub enum Mode {
+  Read,
+  Write,
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
@@ -0,0 +1,13 @@
+//! This is testing that `rename_all` is not supported in numeric enums
+
+use napi_derive::napi;
+
+#[napi(rename_all = "lowercase")]
+pub enum Mode {
+  Read,
+  Write,
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
@@ -0,0 +1,13 @@
+//! This is testing that tuple variants need `content` to be converted
+
+use napi_derive::napi;
+
+#[napi(tag = "kind")]
+pub enum Shape {
+  Circle(f64),
+  Empty,
+}
+
+// Needed for the trybuild tests.
+#[allow(unused)]
+fn main() {}
@@ -8,4 +8,9 @@ mod build_error_tests;
 fn run_build_error_tests() {
   let t = trybuild::TestCases::new();
   t.compile_fail("tests/build_error_tests/ts_arg_type_*.rs");
+  t.compile_fail("tests/build_error_tests/class_extends_*.rs");
+  t.compile_fail("tests/build_error_tests/overload_*.rs");
+  t.compile_fail("tests/build_error_tests/rest_*.rs");
+  t.compile_fail("tests/build_error_tests/string_enum_*.rs");
+  t.compile_fail("tests/build_error_tests/tagged_enum_*.rs");
 }